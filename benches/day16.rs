@@ -0,0 +1,30 @@
+use aoc2019::days::day16;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+// A deterministic 650-digit signal whose message offset lands in the second
+// half of the repeated signal, matching the shape of real puzzle inputs.
+fn signal() -> String {
+    let mut state: u64 = 0x2019_1216;
+    let digits: String = (0..643)
+        .map(|_| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+            char::from(b'0' + ((state >> 33) % 10) as u8)
+        })
+        .collect();
+
+    format!("5970221{}", digits)
+}
+
+fn bench_day16(c: &mut Criterion) {
+    let input = signal();
+
+    c.bench_function("day16 part1", |b| b.iter(|| day16::part1(&input).unwrap()));
+    c.bench_function("day16 part2", |b| b.iter(|| day16::part2(&input).unwrap()));
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = bench_day16
+}
+criterion_main!(benches);
@@ -0,0 +1,36 @@
+use aoc2019::days;
+use aoc2019::input::{self, InputStore};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::path::PathBuf;
+
+// Benchmarks both parts of every registered day that has a local input in
+// `inputs/qNN.txt`. The input cache is not consulted, so results do not depend
+// on the machine's environment.
+fn bench_days(c: &mut Criterion) {
+    let store = InputStore::local(PathBuf::from("inputs"));
+
+    for solution in days::SOLUTIONS {
+        let day = solution.day();
+        let input = match store.find(day) {
+            Some(path) => input::read_file(&path).unwrap(),
+            None => {
+                eprintln!("Skipping day {}: no input found", day);
+                continue;
+            }
+        };
+
+        let mut group = c.benchmark_group(format!("day{:02}", day));
+        group.sample_size(10);
+
+        for part in 1..=2 {
+            group.bench_function(format!("part{}", part), |b| {
+                b.iter(|| solution.run(part, &input).unwrap())
+            });
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
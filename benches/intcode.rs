@@ -0,0 +1,89 @@
+use aoc2019::days;
+use aoc2019::input::{self, InputStore};
+use aoc2019::intcode::{Computer, IndexedParameter};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use std::path::PathBuf;
+
+// Counts the input down to zero, then outputs it. Exercises arithmetic,
+// branching and positional addressing without needing a puzzle input.
+const COUNTDOWN: &[i64] = &[3, 100, 1001, 100, -1, 100, 1005, 100, 2, 4, 100, 99];
+
+const QUINE: &[i64] = &[
+    109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+];
+
+fn load(day: u32) -> Option<String> {
+    let store = InputStore::local(PathBuf::from("inputs"));
+    match store.find(day) {
+        Some(path) => Some(input::read_file(&path).unwrap()),
+        None => {
+            eprintln!("Skipping day {} program: no input found", day);
+            None
+        }
+    }
+}
+
+fn bench_builtin(c: &mut Criterion) {
+    c.bench_function("intcode countdown 100000", |b| {
+        b.iter_batched(
+            || Computer::new(COUNTDOWN),
+            |mut computer| computer.run(vec![100_000]).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    c.bench_function("intcode quine", |b| {
+        b.iter_batched(
+            || Computer::new(QUINE),
+            |mut computer| computer.run(vec![]).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn bench_programs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("intcode programs");
+    group.sample_size(10);
+
+    if let Some(source) = load(9) {
+        let computer = Computer::new_from_str(&source).unwrap();
+        group.bench_function("day09 BOOST", |b| {
+            b.iter_batched(
+                || computer.clone(),
+                |mut computer| computer.run(vec![2]).unwrap(),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+
+    if let Some(source) = load(2) {
+        let computer = Computer::new_from_str(&source).unwrap();
+        group.bench_function("day02 brute force", |b| {
+            b.iter(|| {
+                for noun in 0..100 {
+                    for verb in 0..100 {
+                        let mut computer = computer.clone();
+                        computer
+                            .set_value(IndexedParameter::Positional(1), noun)
+                            .unwrap();
+                        computer
+                            .set_value(IndexedParameter::Positional(2), verb)
+                            .unwrap();
+                        computer.run(vec![]).unwrap();
+                    }
+                }
+            })
+        });
+    }
+
+    if let Some(source) = load(15) {
+        group.bench_function("day15 BFS", |b| {
+            b.iter(|| days::day15::part2(&source).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_builtin, bench_programs);
+criterion_main!(benches);
@@ -0,0 +1,15 @@
+#![no_main]
+
+use aoc2019::intcode::Computer;
+use libfuzzer_sys::fuzz_target;
+
+// Parsing and running arbitrary text must fail cleanly rather than panic,
+// overflow or allocate without bound.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(source) = std::str::from_utf8(data) {
+        if let Ok(mut computer) = Computer::new_from_str(source) {
+            computer.set_instruction_limit(Some(100_000));
+            let _ = computer.run(vec![1, 2, 3]);
+        }
+    }
+});
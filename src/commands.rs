@@ -0,0 +1,308 @@
+//! The subcommands of the `aoc2019` command line tool. Each prints its report
+//! to stdout and fails if anything it checked did not succeed.
+
+#[cfg(feature = "arcade")]
+use crate::common::arcade;
+use crate::common::care_package::Game;
+use crate::common::memory_hack::MemoryMap;
+use crate::common::paddle_ai;
+use crate::common::replay::{self, Replay};
+use crate::days;
+use crate::input::{self, InputStore};
+use crate::verify::{self, Outcome};
+use anyhow::{anyhow, Context, Result};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Lists every implemented day with its title and number of examples.
+pub fn list() {
+    println!("{:>3}  {:<36}  Examples", "Day", "Title");
+    for solution in days::SOLUTIONS {
+        println!(
+            "{:>3}  {:<36}  {}",
+            solution.day(),
+            solution.title(),
+            solution.examples().len()
+        );
+    }
+}
+
+/// Runs every registered example against its published answer.
+pub fn run_examples() -> Result<()> {
+    let mut failures = 0;
+
+    for solution in days::SOLUTIONS {
+        for example in solution.examples() {
+            let status = match solution.run_example(example) {
+                Ok(ref answer) if answer == example.answer => "ok".to_string(),
+                Ok(answer) => {
+                    failures += 1;
+                    format!("expected {}, got {}", example.answer, answer)
+                }
+                Err(error) => {
+                    failures += 1;
+                    format!("error: {:#}", error)
+                }
+            };
+
+            println!("{:>3}  {:>4}  {}", solution.day(), example.part, status);
+        }
+    }
+
+    if failures > 0 {
+        return Err(anyhow!("{} example(s) failed", failures));
+    }
+
+    Ok(())
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// Runs both parts of every day with an input, timing each.
+pub fn run_all(store: &InputStore) -> Result<()> {
+    let mut failures = 0;
+
+    println!("{:>3}  {:>4}  {:>12}  Answer", "Day", "Part", "Time");
+
+    for solution in days::SOLUTIONS {
+        let day = solution.day();
+        let input = match store.find(day).map(|path| input::read_file(&path)) {
+            Some(Ok(input)) => input,
+            Some(Err(error)) => {
+                failures += 2;
+                println!("{:>3}  {:>4}  {:>12}  error: {:#}", day, "-", "-", error);
+                continue;
+            }
+            None => {
+                println!("{:>3}  {:>4}  {:>12}  (missing input)", day, "-", "-");
+                continue;
+            }
+        };
+
+        for part in 1..=2 {
+            let start = Instant::now();
+            let result = solution.run(part, &input);
+            let elapsed = format_duration(start.elapsed());
+
+            let answer = match result {
+                Ok(answer) => answer,
+                Err(error) => {
+                    failures += 1;
+                    format!("error: {:#}", error)
+                }
+            };
+
+            // Multi-line answers (rendered letters) are printed under the row.
+            let mut lines = answer.lines();
+            let first = lines.next().unwrap_or("");
+            println!("{:>3}  {:>4}  {:>12}  {}", day, part, elapsed, first);
+            for line in lines {
+                println!("{:>27}{}", "", line);
+            }
+        }
+    }
+
+    if failures > 0 {
+        return Err(anyhow!("{} part(s) failed", failures));
+    }
+
+    Ok(())
+}
+
+/// Checks every day with an input against the answers recorded at
+/// `answers_path`, and with `record` saves answers for parts that had none.
+pub fn run_verify(answers_path: &Path, record: bool, store: &InputStore) -> Result<()> {
+    let mut answers = verify::Answers::load(answers_path)?;
+    let results = verify::verify_all(days::SOLUTIONS, store, &answers);
+
+    let mut failures = vec![];
+    let mut new_answers = vec![];
+    let mut matched = 0;
+    let mut skipped = 0;
+
+    for result in &results {
+        match &result.outcome {
+            Outcome::Match => matched += 1,
+            Outcome::MissingInput => skipped += 1,
+            Outcome::New(answer) => new_answers.push((result.day, result.part, answer)),
+            Outcome::Mismatch { expected, actual } => failures.push(format!(
+                "{:>3}  {:>4}  expected {:?}, got {:?}",
+                result.day, result.part, expected, actual
+            )),
+            Outcome::Failed(error) => failures.push(format!(
+                "{:>3}  {:>4}  error: {}",
+                result.day, result.part, error
+            )),
+        }
+    }
+
+    println!(
+        "{} matched, {} mismatched or failed, {} new, {} without input",
+        matched,
+        failures.len(),
+        new_answers.len(),
+        skipped / 2
+    );
+
+    if !failures.is_empty() {
+        println!("\nMismatches:");
+        for failure in &failures {
+            println!("{}", failure);
+        }
+    }
+
+    if !new_answers.is_empty() {
+        println!("\nNew answers:");
+        for (day, part, answer) in &new_answers {
+            println!("{:>3}  {:>4}  {:?}", day, part, answer);
+        }
+    }
+
+    if record && !new_answers.is_empty() {
+        for (day, part, answer) in new_answers {
+            answers.insert(day, part, answer.clone());
+        }
+        answers.save(answers_path)?;
+        println!("\nRecorded new answers in {}", answers_path.display());
+    }
+
+    if !failures.is_empty() {
+        return Err(anyhow!("{} part(s) did not match", failures.len()));
+    }
+
+    Ok(())
+}
+
+/// Plays the day 13 game with each autopilot strategy and compares them.
+pub fn compare_autopilots(source: &str) -> Result<()> {
+    let reports = vec![
+        paddle_ai::play(source, &mut paddle_ai::Tracking)?,
+        paddle_ai::play(source, &mut paddle_ai::Predictive::default())?,
+        paddle_ai::play(source, &mut paddle_ai::Planned)?,
+    ];
+
+    println!(
+        "{:<10}  {:>6}  {:>6}  {:>6}  {:>6}  {:>12}  {:>10}",
+        "Strategy", "Score", "Steps", "Moves", "Tilts", "Time", "Frames/s"
+    );
+    for report in &reports {
+        println!(
+            "{:<10}  {:>6}  {:>6}  {:>6}  {:>6}  {:>12}  {:>10.0}",
+            report.strategy,
+            report.score,
+            report.steps,
+            report.moves,
+            report.tilts,
+            format_duration(report.elapsed),
+            report.throughput()
+        );
+    }
+
+    Ok(())
+}
+
+/// Reports where the day 13 program keeps its playfield, and with `play`
+/// lets the game play itself with the paddle's row walled off.
+pub fn run_hack(source: &str, play: bool) -> Result<()> {
+    let map = MemoryMap::analyze(source)?;
+    let tiles = map.tile_map();
+    println!(
+        "Tile map: {}x{} at addresses {}..{}",
+        map.bounds.width(),
+        map.bounds.height(),
+        tiles.start,
+        tiles.end
+    );
+    match map.scores {
+        Some(address) => println!("Score table: {}..{}", address, address + tiles.len()),
+        None => println!("Score table: not found after the tile map"),
+    }
+
+    let patches = map.wall_paddle_row();
+    println!(
+        "Paddle row {}: {} patches to make it a wall",
+        map.paddle.y,
+        patches.len()
+    );
+
+    if play {
+        let game = Game::with_patches(source, true, &patches)?;
+        let report = paddle_ai::play_game(game, &mut paddle_ai::Idle)?;
+        println!(
+            "Played itself: final score {} after {} moves in {}",
+            report.score,
+            report.moves,
+            format_duration(report.elapsed)
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "arcade")]
+/// Plays the day 13 game in the terminal, optionally saving a replay.
+pub fn run_arcade(
+    source: &str,
+    autoplay: bool,
+    frame_ms: u64,
+    record: Option<PathBuf>,
+) -> Result<()> {
+    let mut game = Game::new(source, true)?;
+    if record.is_some() {
+        game.start_recording()?;
+    }
+
+    arcade::play_in_terminal(&mut game, autoplay, Duration::from_millis(frame_ms))?;
+    println!("Final score: {}", game.screen.score);
+
+    if let (Some(path), Some(replay)) = (record, game.replay()) {
+        std::fs::write(&path, replay.to_string())
+            .with_context(|| format!("Unable to write replay {}", path.display()))?;
+        println!("Saved {} moves to {}", replay.moves.len(), path.display());
+    }
+
+    Ok(())
+}
+
+/// Plays back a saved replay against `source`, optionally writing every
+/// frame as text or as PPM images.
+pub fn run_replay(
+    source: &str,
+    replay_path: &Path,
+    text: Option<PathBuf>,
+    ppm: Option<PathBuf>,
+    scale: usize,
+) -> Result<()> {
+    let replay: Replay = input::read_file(replay_path)?
+        .parse()
+        .with_context(|| format!("Invalid replay {}", replay_path.display()))?;
+
+    let frames = replay.play_back(source)?;
+    let last = frames.last().unwrap();
+    println!(
+        "Verified {} moves: final score {} with {} blocks left",
+        replay.moves.len(),
+        last.score,
+        last.remaining_blocks
+    );
+
+    if let Some(path) = text {
+        let mut out = BufWriter::new(
+            File::create(&path).with_context(|| format!("Unable to create {}", path.display()))?,
+        );
+        replay::write_text(&frames, &mut out)?;
+        out.flush()?;
+        println!("Wrote {} text frames to {}", frames.len(), path.display());
+    }
+
+    if let Some(dir) = ppm {
+        let count = replay::write_ppm_frames(&frames, &dir, scale)?;
+        println!("Wrote {} images to {}", count, dir.display());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,186 @@
+//! An interactive terminal front end for the care package [`Game`].
+//!
+//! The game advances one frame per tick, using at most one key per tick: the
+//! arrow keys tilt the joystick, `a` toggles the autopilot and `q` quits.
+//! [`play`] takes the keys as an iterator, so a scripted sequence can drive
+//! it without a terminal. Reading keys from a real terminal with
+//! `play_in_terminal` needs the `arcade` feature.
+
+use crate::common::care_package::{autopilot, Game};
+use crate::common::Direction;
+use anyhow::Result;
+use std::io::Write;
+
+#[cfg(feature = "arcade")]
+pub use terminal::play_in_terminal;
+
+// Clears the screen and moves the cursor to the top left.
+const CLEAR: &str = "\x1b[2J\x1b[1;1H";
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Key {
+    Left,
+    Right,
+    Autoplay,
+    Quit,
+}
+
+/// Plays until the game halts, the player quits or the keys run out. Each
+/// item is the key pressed during one frame, or `None` to leave the joystick
+/// neutral (or to the autopilot, when it is on).
+pub fn play<K, W>(game: &mut Game, mut autoplay: bool, keys: K, out: &mut W) -> Result<()>
+where
+    K: IntoIterator<Item = Option<Key>>,
+    W: Write,
+{
+    let mut keys = keys.into_iter();
+    draw(game, autoplay, out)?;
+
+    while !game.is_over() {
+        let key = match keys.next() {
+            Some(Some(Key::Quit)) | None => break,
+            Some(key) => key,
+        };
+
+        if key == Some(Key::Autoplay) {
+            autoplay = !autoplay;
+        }
+
+        let tilt = match key {
+            Some(Key::Left) => Some(Direction::West),
+            Some(Key::Right) => Some(Direction::East),
+            _ if autoplay => autopilot(&game.screen),
+            _ => None,
+        };
+
+        game.do_move(tilt)?;
+        draw(game, autoplay, out)?;
+    }
+
+    Ok(())
+}
+
+fn draw<W: Write>(game: &Game, autoplay: bool, out: &mut W) -> Result<()> {
+    let screen = &game.screen;
+
+    // Raw mode does not translate newlines, so every line returns the cursor.
+    write!(out, "{}", CLEAR)?;
+    for line in screen.to_string().lines() {
+        write!(out, "{}\r\n", line)?;
+    }
+
+    let mode = if autoplay { "autoplay" } else { "manual" };
+    write!(
+        out,
+        "Score: {}  Blocks: {}  [{}]\r\n",
+        screen.score, screen.remaining_blocks, mode
+    )?;
+    write!(out, "←/→ move  a autoplay  q quit\r\n")?;
+
+    out.flush()?;
+    Ok(())
+}
+
+#[cfg(feature = "arcade")]
+mod terminal {
+    use super::{play, Key};
+    use crate::common::care_package::Game;
+    use anyhow::Result;
+    use std::io;
+    use std::thread;
+    use std::time::Duration;
+    use termion::cursor;
+    use termion::event;
+    use termion::input::TermRead;
+    use termion::raw::IntoRawMode;
+
+    fn key_from_event(key: event::Key) -> Option<Key> {
+        match key {
+            event::Key::Left | event::Key::Char('h') => Some(Key::Left),
+            event::Key::Right | event::Key::Char('l') => Some(Key::Right),
+            event::Key::Char('a') => Some(Key::Autoplay),
+            event::Key::Char('q') | event::Key::Esc | event::Key::Ctrl('c') => Some(Key::Quit),
+            _ => None,
+        }
+    }
+
+    /// Plays on the terminal in raw mode, advancing a frame every `frame`. The
+    /// last key pressed during a frame is the one used.
+    pub fn play_in_terminal(game: &mut Game, autoplay: bool, frame: Duration) -> Result<()> {
+        let mut out = cursor::HideCursor::from(io::stdout().into_raw_mode()?);
+        let mut input = termion::async_stdin().keys();
+
+        let keys = std::iter::from_fn(|| {
+            thread::sleep(frame);
+
+            let mut pressed = None;
+            for event in input.by_ref() {
+                match event.map(key_from_event) {
+                    Ok(Some(Key::Quit)) | Err(_) => return Some(Some(Key::Quit)),
+                    Ok(Some(key)) => pressed = Some(key),
+                    Ok(None) => {}
+                }
+            }
+
+            Some(pressed)
+        });
+
+        play(game, autoplay, keys, &mut out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Draws a ball at (3, 0), then repeatedly reads the joystick, moves a
+    // paddle on row 1 and reports its x as the score, halting at x = 3.
+    const PROGRAM: &str = "104,3,104,0,104,4,\
+                           3,101,1,100,101,100,\
+                           104,-1,104,0,4,100,\
+                           4,100,104,1,104,3,\
+                           1008,100,3,102,1005,102,34,1105,1,6,99";
+
+    #[test]
+    fn test_scripted_game() -> Result<()> {
+        let mut game = Game::new(PROGRAM, false)?;
+        let mut out = vec![];
+
+        // One manual move, an idle frame, then the autopilot finishes. The
+        // trailing keys are never read.
+        let keys = vec![
+            Some(Key::Right),
+            None,
+            Some(Key::Autoplay),
+            None,
+            Some(Key::Quit),
+        ];
+        play(&mut game, false, keys, &mut out)?;
+
+        assert!(game.is_over());
+        assert_eq!(game.screen.score, 3);
+
+        let out = String::from_utf8(out)?;
+        assert_eq!(out.matches("Score:").count(), 5);
+        assert!(out.contains("Score: 1  Blocks: 0  [manual]"));
+        assert!(
+            out.ends_with("Score: 3  Blocks: 0  [autoplay]\r\n←/→ move  a autoplay  q quit\r\n")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_quit() -> Result<()> {
+        let mut game = Game::new(PROGRAM, false)?;
+        play(
+            &mut game,
+            true,
+            vec![None, Some(Key::Quit), None],
+            &mut vec![],
+        )?;
+
+        assert!(!game.is_over());
+        assert_eq!(game.screen.paddle.x, 1);
+        Ok(())
+    }
+}
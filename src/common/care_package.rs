@@ -1,11 +1,31 @@
-use crate::common::Point;
-use crate::intcode::{Computer, IndexedParameter};
-use anyhow::Result;
-use itertools::join;
+//! The day 13 arcade cabinet, driven by an Intcode program.
+//!
+//! ```
+//! use aoc2019::common::care_package::Game;
+//! use aoc2019::common::Point;
+//!
+//! // Draws a paddle at (0, 0) and a ball at (1, 0), then halts.
+//! let game = Game::new("104,0,104,0,104,3,104,1,104,0,104,4,99", false)?;
+//! assert_eq!(game.screen.paddle, Point { x: 0, y: 0 });
+//! assert_eq!(game.screen.ball, Point { x: 1, y: 0 });
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use crate::common::grid::Bounds;
+use crate::common::replay::{Move, Replay};
+use crate::common::{Direction, Grid, Point, YAxis};
+use crate::intcode::{Computer, IndexedParameter, StoppedResult};
+use anyhow::{anyhow, Result};
+use std::convert::TryFrom;
 use std::fmt;
+use std::io::{self, Write};
+
+// Screen coordinates beyond this distance from the origin are rejected, since
+// the screen stores every cell of the area drawn.
+const MAX_COORDINATE: i64 = 1024;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
-enum Tile {
+pub(crate) enum Tile {
     Empty,
     Wall,
     Block,
@@ -13,32 +33,131 @@ enum Tile {
     Ball,
 }
 
-#[derive(Copy, Clone, Debug)]
-pub enum Direction {
-    Neutral,
+// A joystick position. The joystick only tilts sideways, so moves are checked
+// against it before they reach the program or a replay.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Joystick {
     Left,
+    Neutral,
     Right,
 }
 
+impl Joystick {
+    // The cabinet's encoding of the position.
+    fn input(self) -> i64 {
+        match self {
+            Joystick::Left => -1,
+            Joystick::Neutral => 0,
+            Joystick::Right => 1,
+        }
+    }
+
+    pub(crate) fn tilt(self) -> Option<Direction> {
+        match self {
+            Joystick::Left => Some(Direction::West),
+            Joystick::Neutral => None,
+            Joystick::Right => Some(Direction::East),
+        }
+    }
+}
+
+// Tilts west (left) or east (right), or stays neutral for `None`.
+impl TryFrom<Option<Direction>> for Joystick {
+    type Error = anyhow::Error;
+
+    fn try_from(tilt: Option<Direction>) -> Result<Self> {
+        match tilt {
+            None => Ok(Joystick::Neutral),
+            Some(Direction::West) => Ok(Joystick::Left),
+            Some(Direction::East) => Ok(Joystick::Right),
+            Some(direction) => Err(anyhow!("The joystick cannot tilt {:?}", direction)),
+        }
+    }
+}
+
+impl TryFrom<i64> for Tile {
+    type Error = anyhow::Error;
+
+    fn try_from(id: i64) -> Result<Self> {
+        match id {
+            0 => Ok(Tile::Empty),
+            1 => Ok(Tile::Wall),
+            2 => Ok(Tile::Block),
+            3 => Ok(Tile::Paddle),
+            4 => Ok(Tile::Ball),
+            _ => Err(anyhow!("Unknown tile id {}", id)),
+        }
+    }
+}
+
+impl Tile {
+    fn symbol(self) -> char {
+        match self {
+            Tile::Empty => ' ',
+            Tile::Wall => '█',
+            Tile::Block => '░',
+            Tile::Paddle => '▄',
+            Tile::Ball => '■',
+        }
+    }
+
+    /// The ID the program draws this tile with.
+    pub(crate) fn id(self) -> i64 {
+        match self {
+            Tile::Empty => 0,
+            Tile::Wall => 1,
+            Tile::Block => 2,
+            Tile::Paddle => 3,
+            Tile::Ball => 4,
+        }
+    }
+
+    fn color(self) -> [u8; 3] {
+        match self {
+            Tile::Empty => [0, 0, 0],
+            Tile::Wall => [128, 128, 128],
+            Tile::Block => [200, 120, 40],
+            Tile::Paddle => [230, 230, 230],
+            Tile::Ball => [220, 40, 40],
+        }
+    }
+}
+
+// One output triple: a new score, or a tile drawn at a point.
+#[derive(Copy, Clone, Debug)]
+enum Command {
+    Score(u64),
+    Draw(Point, Tile),
+}
+
+impl Command {
+    fn parse(x: i64, y: i64, value: i64) -> Result<Self> {
+        if (x, y) == (-1, 0) {
+            let score = u64::try_from(value).map_err(|_| anyhow!("Invalid score {}", value))?;
+            return Ok(Command::Score(score));
+        }
+
+        if x.abs() > MAX_COORDINATE || y.abs() > MAX_COORDINATE {
+            return Err(anyhow!("Tile drawn off screen at {},{}", x, y));
+        }
+
+        Ok(Command::Draw(Point { x, y }, Tile::try_from(value)?))
+    }
+}
+
 impl fmt::Display for Tile {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Tile::Empty => " ",
-                Tile::Wall => "█",
-                Tile::Block => "░",
-                Tile::Paddle => "▄",
-                Tile::Ball => "■",
-            }
-        )
+        write!(f, "{}", self.symbol())
     }
 }
 
+/// The cabinet's display, updated from the program's output triples. It
+/// grows to fit whatever the program draws within 1024 cells of the origin.
 #[derive(Clone, Debug)]
 pub struct Screen {
-    data: Vec<Vec<Tile>>,
+    data: Grid<Tile>,
+    // Output left over when a program stops partway through a triple.
+    pending: Vec<i64>,
     pub score: u64,
 
     // Cached data from initial traversal
@@ -50,7 +169,8 @@ pub struct Screen {
 impl Screen {
     pub fn new() -> Self {
         Self {
-            data: vec![vec![Tile::Empty; 38]; 22],
+            data: Grid::dense(YAxis::Down),
+            pending: vec![],
             score: 0,
 
             paddle: Point { x: 0, y: 0 },
@@ -59,84 +179,366 @@ impl Screen {
         }
     }
 
-    pub fn update(&mut self, output: Vec<i64>) {
-        for chunk in output.chunks(3) {
-            if chunk[0] == -1 && chunk[1] == 0 {
-                self.score = chunk[2] as u64;
-                continue;
-            }
+    /// Applies a batch of draw commands. A trailing incomplete triple is kept
+    /// and completed by the next batch. The whole batch is checked first, so
+    /// an invalid command leaves the screen as it was.
+    pub fn update(&mut self, output: Vec<i64>) -> Result<()> {
+        let mut values: Vec<i64> = self.pending.iter().copied().chain(output).collect();
+        let complete = values.len() - values.len() % 3;
+        let commands = values[..complete]
+            .chunks(3)
+            .map(|chunk| Command::parse(chunk[0], chunk[1], chunk[2]))
+            .collect::<Result<Vec<_>>>()?;
+        self.pending = values.split_off(complete);
 
-            let point = Point {
-                x: chunk[0],
-                y: chunk[1],
+        for command in commands {
+            let (point, next_tile) = match command {
+                Command::Score(score) => {
+                    self.score = score;
+                    continue;
+                }
+                Command::Draw(point, tile) => (point, tile),
             };
 
-            let current_tile = self.data[point.y as usize][point.x as usize];
-            let next_tile = match chunk[2] {
-                0 => Tile::Empty,
-                1 => Tile::Wall,
-                2 => Tile::Block,
-                3 => Tile::Paddle,
-                4 => Tile::Ball,
-                _ => unreachable!(),
-            };
+            let current_tile = self.data.get(&point).copied().unwrap_or(Tile::Empty);
 
-            if current_tile == Tile::Block && next_tile == Tile::Empty {
+            if current_tile == Tile::Block && next_tile != Tile::Block {
                 self.remaining_blocks -= 1;
             } else if current_tile != Tile::Block && next_tile == Tile::Block {
                 self.remaining_blocks += 1;
-            } else if next_tile == Tile::Paddle {
-                self.paddle = point;
-            } else if next_tile == Tile::Ball {
-                self.ball = point;
             }
 
-            self.data[point.y as usize][point.x as usize] = next_tile;
+            match next_tile {
+                Tile::Paddle => self.paddle = point,
+                Tile::Ball => self.ball = point,
+                _ => {}
+            }
+
+            self.data.insert(point, next_tile);
         }
+
+        Ok(())
+    }
+
+    /// The ID the program drew at `point` with, or 0 (empty) if it drew
+    /// nothing there.
+    pub fn tile_id(&self, point: &Point) -> i64 {
+        self.data.get(point).map_or(0, |tile| tile.id())
+    }
+
+    /// The area drawn so far, or `None` before anything is drawn.
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.data.bounds()
+    }
+
+    /// Writes the part of the screen within `bounds` as a binary PPM image,
+    /// with each tile drawn as a `scale` by `scale` square.
+    pub fn write_ppm<W: Write>(&self, bounds: Bounds, scale: usize, out: &mut W) -> io::Result<()> {
+        write!(
+            out,
+            "P6\n{} {}\n255\n",
+            bounds.width() * scale,
+            bounds.height() * scale
+        )?;
+
+        for y in bounds.min.y..=bounds.max.y {
+            let mut row = Vec::with_capacity(bounds.width() * scale * 3);
+            for x in bounds.min.x..=bounds.max.x {
+                let tile = self.data.get(&Point { x, y }).copied();
+                let color = tile.unwrap_or(Tile::Empty).color();
+                for _ in 0..scale {
+                    row.extend_from_slice(&color);
+                }
+            }
+
+            for _ in 0..scale {
+                out.write_all(&row)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Screen {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl fmt::Display for Screen {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            join(self.data.iter().map(|row| join(row, "")), "\n")
-        )
+        let rendered = self
+            .data
+            .render(|tile| tile.map_or(Tile::Empty.symbol(), |tile| tile.symbol()));
+        write!(f, "{}", rendered)
     }
 }
 
+/// A running game. Cloning it forks the game, including the program state.
 #[derive(Debug, Clone)]
 pub struct Game {
     computer: Computer,
+    free_play: bool,
+    halted: bool,
+    moves: usize,
+    replay: Option<Replay>,
     pub screen: Screen,
 }
 
 impl Game {
+    /// Loads the game and draws the initial screen. With `has_credits` the
+    /// cabinet is set to free play.
     pub fn new(initial_memory: &str, has_credits: bool) -> Result<Self> {
+        Self::with_patches(initial_memory, has_credits, &[])
+    }
+
+    /// Loads the game like [`new`](Self::new), but first writes each
+    /// `(address, value)` patch into the program's memory.
+    pub fn with_patches(
+        initial_memory: &str,
+        has_credits: bool,
+        patches: &[(usize, i64)],
+    ) -> Result<Self> {
         let mut computer = Computer::new_from_str(initial_memory)?;
         if has_credits {
-            computer.set_value(IndexedParameter::Positional(0), 2);
+            computer.set_value(IndexedParameter::Positional(0), 2)?;
         }
 
-        let (_, initial_output) = computer.run_until_stopped(vec![])?;
+        for &(address, value) in patches {
+            computer.set_value(IndexedParameter::Positional(address), value)?;
+        }
+
+        let (result, initial_output) = computer.run_until_stopped(vec![])?;
 
         let mut screen = Screen::new();
-        screen.update(initial_output);
+        screen.update(initial_output)?;
+
+        Ok(Self {
+            computer,
+            free_play: has_credits,
+            halted: result == StoppedResult::Halted,
+            moves: 0,
+            replay: None,
+            screen,
+        })
+    }
+
+    /// The number of moves made so far.
+    pub fn moves(&self) -> usize {
+        self.moves
+    }
+
+    /// Starts recording every move into a [`Replay`]. Replays always start
+    /// from a new game, so this fails once a move has been made.
+    pub fn start_recording(&mut self) -> Result<()> {
+        if self.moves > 0 {
+            return Err(anyhow!("Recording must start before the first move"));
+        }
+
+        self.replay = Some(Replay {
+            free_play: self.free_play,
+            moves: vec![],
+        });
+        Ok(())
+    }
+
+    /// The moves recorded so far, if recording was started.
+    pub fn replay(&self) -> Option<&Replay> {
+        self.replay.as_ref()
+    }
 
-        Ok(Self { computer, screen })
+    /// The program's memory image.
+    pub fn memory(&self) -> &[i64] {
+        self.computer.memory()
     }
 
-    pub fn do_move(&mut self, direction: Direction) -> Result<&Screen> {
-        let input = vec![match direction {
-            Direction::Neutral => 0,
-            Direction::Left => -1,
-            Direction::Right => 1,
-        }];
+    /// Whether the program has halted, so further moves have no effect.
+    pub fn is_over(&self) -> bool {
+        self.halted
+    }
+
+    /// A copy of the game to simulate ahead with. Unlike a clone it does not
+    /// carry on the recording.
+    pub fn fork(&self) -> Self {
+        Self {
+            computer: self.computer.clone(),
+            replay: None,
+            screen: self.screen.clone(),
+            ..*self
+        }
+    }
+
+    /// Makes several moves in a single run of the program, so the screen only
+    /// reflects the last of them. Moves left over when the game ends are
+    /// ignored, and not counted by [`moves`](Self::moves). While recording,
+    /// the moves are made one at a time so that each is recorded with its
+    /// score.
+    pub fn do_moves(&mut self, tilts: &[Option<Direction>]) -> Result<&Screen> {
+        if self.replay.is_some() {
+            for &tilt in tilts {
+                self.do_move(tilt)?;
+            }
+
+            return Ok(&self.screen);
+        }
+
+        let input = tilts
+            .iter()
+            .map(|&tilt| Joystick::try_from(tilt).map(Joystick::input))
+            .collect::<Result<Vec<_>>>()?;
+        let mut input = input.into_iter();
+        let (result, output) = self.computer.run_until_stopped_from(&mut input)?;
+        self.halted = result == StoppedResult::Halted;
+        self.screen.update(output)?;
+        self.moves += tilts.len() - input.len();
+
+        Ok(&self.screen)
+    }
+
+    /// Tilts the joystick west or east, or leaves it neutral for `None`, and
+    /// runs until the game next waits for input. The joystick cannot tilt in
+    /// any other direction. Once the game is over this does nothing, and the
+    /// move is neither counted nor recorded.
+    pub fn do_move(&mut self, tilt: Option<Direction>) -> Result<&Screen> {
+        if self.halted {
+            return Ok(&self.screen);
+        }
+
+        let joystick = Joystick::try_from(tilt)?;
+        let (result, output) = self.computer.run_until_stopped(vec![joystick.input()])?;
+        self.halted = result == StoppedResult::Halted;
+        self.screen.update(output)?;
+        self.moves += 1;
 
-        let (_, output) = self.computer.run_until_stopped(input)?;
-        self.screen.update(output);
+        if let Some(replay) = &mut self.replay {
+            replay.moves.push(Move {
+                joystick,
+                score: self.screen.score,
+                remaining_blocks: self.screen.remaining_blocks,
+            });
+        }
 
         Ok(&self.screen)
     }
 }
+
+/// Tilts the joystick towards the ball, keeping the paddle under it.
+pub fn autopilot(screen: &Screen) -> Option<Direction> {
+    if screen.paddle.x < screen.ball.x {
+        Some(Direction::East)
+    } else if screen.paddle.x > screen.ball.x {
+        Some(Direction::West)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_screen_render() -> Result<()> {
+        // Draws a wall, a block and a paddle on the top row and a ball below.
+        let program = "104,0,104,0,104,1,104,1,104,0,104,2,104,2,104,0,104,3,\
+                       104,1,104,1,104,4,104,-1,104,0,104,7,99";
+        let game = Game::new(program, false)?;
+
+        assert_eq!(game.screen.to_string(), "█░▄\n ■ ");
+        assert_eq!(game.screen.remaining_blocks, 1);
+        assert_eq!(game.screen.score, 7);
+        Ok(())
+    }
+
+    #[test]
+    fn test_screen_grows() -> Result<()> {
+        let mut screen = Screen::new();
+        screen.update(vec![0, 0, 1, 99, 40, 2, -3, 5, 4])?;
+
+        assert_eq!(
+            screen.bounds(),
+            Some(Bounds {
+                min: Point { x: -3, y: 0 },
+                max: Point { x: 99, y: 40 },
+            })
+        );
+        assert_eq!(screen.remaining_blocks, 1);
+        assert_eq!(screen.ball, Point { x: -3, y: 5 });
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_output() -> Result<()> {
+        let mut screen = Screen::new();
+        screen.update(vec![2, 3])?;
+        assert_eq!(screen.bounds(), None);
+
+        screen.update(vec![2, -1, 0])?;
+        assert_eq!(screen.remaining_blocks, 1);
+
+        screen.update(vec![12345])?;
+        assert_eq!(screen.score, 12345);
+
+        // A block replaced by the ball is gone.
+        screen.update(vec![2, 3, 4])?;
+        assert_eq!(screen.remaining_blocks, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_output() -> Result<()> {
+        assert!(Screen::new().update(vec![0, 0, 5]).is_err());
+        assert!(Screen::new().update(vec![-1, 0, -7]).is_err());
+        assert!(Screen::new().update(vec![1_000_000, 1_000_000, 1]).is_err());
+
+        // Nothing from a rejected batch is applied, and what was pending
+        // before it still is.
+        let mut screen = Screen::new();
+        screen.update(vec![0, 0, 2, 1])?;
+        assert!(screen.update(vec![0, 2, -1, 0, 50, 2, 0, 9]).is_err());
+        assert_eq!(screen.remaining_blocks, 1);
+        assert_eq!(screen.score, 0);
+        assert_eq!(screen.bounds(), Some(Bounds::around(Point { x: 0, y: 0 })));
+
+        screen.update(vec![0, 1])?;
+        assert_eq!(screen.to_string(), "░█");
+        Ok(())
+    }
+
+    #[test]
+    fn test_moves_after_halt_are_not_counted() -> Result<()> {
+        // Reads the joystick once, then halts.
+        let program = "3,3,99,0";
+        let tilts = [Some(Direction::West); 3];
+
+        let mut game = Game::new(program, false)?;
+        game.do_moves(&tilts)?;
+        assert!(game.is_over());
+        assert_eq!(game.moves(), 1);
+
+        game.do_move(None)?;
+        assert_eq!(game.moves(), 1);
+
+        let mut recorded = Game::new(program, false)?;
+        recorded.start_recording()?;
+        recorded.do_moves(&tilts)?;
+        recorded.do_move(None)?;
+        assert_eq!(recorded.moves(), 1);
+        assert_eq!(recorded.replay().unwrap().moves.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_joystick_encoding() -> Result<()> {
+        assert_eq!(Joystick::try_from(None)?.input(), 0);
+        assert_eq!(Joystick::try_from(Some(Direction::West))?.input(), -1);
+        assert_eq!(Joystick::try_from(Some(Direction::East))?.input(), 1);
+
+        let mut game = Game::new("3,3,99,0", false)?;
+        assert!(game.do_move(Some(Direction::North)).is_err());
+        assert!(game.do_moves(&[None, Some(Direction::South)]).is_err());
+        assert_eq!(game.moves(), 0);
+        Ok(())
+    }
+}
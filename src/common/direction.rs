@@ -0,0 +1,177 @@
+//! Compass headings on a grid.
+
+use crate::common::{Point, YAxis};
+
+/// A compass heading. Which way North points in coordinates depends on the
+/// [`YAxis`] convention in use.
+///
+/// Puzzles that feed directions to an Intcode program each define their own
+/// input encoding as a `TryFrom<Direction>` conversion.
+///
+/// ```
+/// use aoc2019::common::{Direction, Point, YAxis};
+///
+/// let heading = Direction::from_char('^').unwrap();
+/// assert_eq!(heading, Direction::North);
+/// assert_eq!(heading.rotate_right(), Direction::East);
+/// assert_eq!(heading.opposite(), Direction::South);
+///
+/// assert_eq!(heading.offset(YAxis::Up), Point { x: 0, y: 1 });
+/// assert_eq!(heading.offset(YAxis::Down), Point { x: 0, y: -1 });
+/// ```
+#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
+pub enum Direction {
+    North,
+    South,
+    West,
+    East,
+    NorthWest,
+    NorthEast,
+    SouthWest,
+    SouthEast,
+}
+
+impl Direction {
+    /// The four cardinal directions.
+    pub fn all() -> &'static [Direction] {
+        &[
+            Direction::North,
+            Direction::South,
+            Direction::West,
+            Direction::East,
+        ]
+    }
+
+    /// The cardinal directions followed by the four diagonals.
+    pub fn all_with_diagonals() -> &'static [Direction] {
+        &[
+            Direction::North,
+            Direction::South,
+            Direction::West,
+            Direction::East,
+            Direction::NorthWest,
+            Direction::NorthEast,
+            Direction::SouthWest,
+            Direction::SouthEast,
+        ]
+    }
+
+    /// Parses arrows (`^v<>`), `UDLR` or compass letters (`NSWE`).
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' | 'U' | 'N' => Some(Direction::North),
+            'v' | 'D' | 'S' => Some(Direction::South),
+            '<' | 'L' | 'W' => Some(Direction::West),
+            '>' | 'R' | 'E' => Some(Direction::East),
+            _ => None,
+        }
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        matches!(
+            self,
+            Direction::NorthWest
+                | Direction::NorthEast
+                | Direction::SouthWest
+                | Direction::SouthEast
+        )
+    }
+
+    /// The offset of one step in this direction under the given convention.
+    pub fn offset(&self, y_axis: YAxis) -> Point {
+        let (x, north) = match self {
+            Direction::North => (0, 1),
+            Direction::South => (0, -1),
+            Direction::West => (-1, 0),
+            Direction::East => (1, 0),
+            Direction::NorthWest => (-1, 1),
+            Direction::NorthEast => (1, 1),
+            Direction::SouthWest => (-1, -1),
+            Direction::SouthEast => (1, -1),
+        };
+
+        match y_axis {
+            YAxis::Up => Point { x, y: north },
+            YAxis::Down => Point { x, y: -north },
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.rotate_left().rotate_left()
+    }
+
+    /// Turns 90 degrees counterclockwise.
+    pub fn rotate_left(&self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::South => Direction::East,
+            Direction::West => Direction::South,
+            Direction::East => Direction::North,
+            Direction::NorthWest => Direction::SouthWest,
+            Direction::NorthEast => Direction::NorthWest,
+            Direction::SouthWest => Direction::SouthEast,
+            Direction::SouthEast => Direction::NorthEast,
+        }
+    }
+
+    /// Turns 90 degrees clockwise.
+    pub fn rotate_right(&self) -> Self {
+        self.opposite().rotate_left()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offsets_match_rotations() {
+        for direction in Direction::all_with_diagonals() {
+            let offset = direction.offset(YAxis::Up);
+            assert_eq!(
+                direction.rotate_left().offset(YAxis::Up),
+                offset.rotate_left()
+            );
+            assert_eq!(
+                direction.rotate_right().offset(YAxis::Up),
+                offset.rotate_right()
+            );
+            assert_eq!(direction.opposite().offset(YAxis::Up), -offset);
+            assert_eq!(offset.chebyshev_length(), 1);
+            assert_eq!(direction.is_diagonal(), offset.manhattan_length() == 2);
+        }
+    }
+
+    #[test]
+    fn test_screen_offsets() {
+        // On screen, a left turn is clockwise and North is towards row zero.
+        for direction in Direction::all_with_diagonals() {
+            let up = direction.offset(YAxis::Up);
+            let down = direction.offset(YAxis::Down);
+            assert_eq!(down, Point { x: up.x, y: -up.y });
+            assert_eq!(
+                direction.rotate_left().offset(YAxis::Down),
+                down.rotate_right()
+            );
+        }
+
+        assert_eq!(Direction::North.offset(YAxis::Down), Point { x: 0, y: -1 });
+    }
+
+    #[test]
+    fn test_from_char() {
+        for (chars, direction) in &[
+            ("^UN", Direction::North),
+            ("vDS", Direction::South),
+            ("<LW", Direction::West),
+            (">RE", Direction::East),
+        ] {
+            for c in chars.chars() {
+                assert_eq!(Direction::from_char(c), Some(*direction));
+            }
+        }
+
+        assert_eq!(Direction::from_char('x'), None);
+        assert_eq!(Direction::from_char('u'), None);
+    }
+}
@@ -0,0 +1,525 @@
+//! Two-dimensional grids and parsing of ASCII puzzle maps.
+
+use crate::common::{Direction, Point, YAxis};
+use std::collections::HashMap;
+
+/// Offsets to the four orthogonal neighbors, in reading order.
+pub const NEIGHBORS4: [Point; 4] = [
+    Point { x: 0, y: -1 },
+    Point { x: -1, y: 0 },
+    Point { x: 1, y: 0 },
+    Point { x: 0, y: 1 },
+];
+
+/// Offsets to the eight orthogonal and diagonal neighbors, in reading order.
+pub const NEIGHBORS8: [Point; 8] = [
+    Point { x: -1, y: -1 },
+    Point { x: 0, y: -1 },
+    Point { x: 1, y: -1 },
+    Point { x: -1, y: 0 },
+    Point { x: 1, y: 0 },
+    Point { x: -1, y: 1 },
+    Point { x: 0, y: 1 },
+    Point { x: 1, y: 1 },
+];
+
+/// An inclusive rectangle of points.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    /// The smallest bounds containing a single point.
+    pub fn around(point: Point) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1) as usize
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// The smallest bounds containing both these bounds and `point`.
+    pub fn including(&self, point: Point) -> Self {
+        Self {
+            min: Point {
+                x: self.min.x.min(point.x),
+                y: self.min.y.min(point.y),
+            },
+            max: Point {
+                x: self.max.x.max(point.x),
+                y: self.max.y.max(point.y),
+            },
+        }
+    }
+
+    /// Every point in the bounds, row by row from the smallest y.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (min, max) = (self.min, self.max);
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point { x, y }))
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Cells<T> {
+    Sparse(HashMap<Point, T>),
+    Dense {
+        bounds: Option<Bounds>,
+        cells: Vec<Option<T>>,
+        len: usize,
+    },
+}
+
+/// A map from points to tiles.
+///
+/// Sparse grids suit unbounded exploration, such as a robot wandering an
+/// unknown area. Dense grids store a rectangle of cells and suit maps whose
+/// extent is known up front; writing outside the rectangle grows it, at least
+/// doubling its size in that direction. Either way, cells that were never set
+/// are simply absent.
+///
+/// Every grid has a [`YAxis`] convention, chosen when it is created, which
+/// both [`step`](Grid::step) and [`render`](Grid::render) follow.
+///
+/// ```
+/// use aoc2019::common::{Direction, Grid, Point};
+///
+/// let mut grid = Grid::parse("#.\n.#", |c| if c == '#' { Some(true) } else { None });
+/// let below = grid.step(Point { x: 1, y: 0 }, Direction::South);
+/// grid.insert(Point { x: 2, y: 0 }, false);
+///
+/// assert_eq!(grid.get(&below), Some(&true));
+/// assert_eq!(grid.len(), 3);
+/// assert_eq!(grid.neighbors4(Point { x: 1, y: 0 }).count(), 3);
+/// assert_eq!(
+///     grid.render(|tile| match tile {
+///         Some(true) => '#',
+///         Some(false) => 'o',
+///         None => '.',
+///     }),
+///     "#.o\n.#."
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Grid<T> {
+    cells: Cells<T>,
+    y_axis: YAxis,
+}
+
+impl<T> Grid<T> {
+    pub fn sparse(y_axis: YAxis) -> Self {
+        Self {
+            cells: Cells::Sparse(HashMap::new()),
+            y_axis,
+        }
+    }
+
+    pub fn dense(y_axis: YAxis) -> Self {
+        Self {
+            cells: Cells::Dense {
+                bounds: None,
+                cells: vec![],
+                len: 0,
+            },
+            y_axis,
+        }
+    }
+
+    /// A dense grid with room for every point in `bounds`.
+    pub fn dense_with_bounds(bounds: Bounds, y_axis: YAxis) -> Self {
+        Self {
+            cells: Cells::Dense {
+                bounds: Some(bounds),
+                cells: (0..bounds.width() * bounds.height())
+                    .map(|_| None)
+                    .collect(),
+                len: 0,
+            },
+            y_axis,
+        }
+    }
+
+    /// Parses an ASCII map into a dense grid, with x increasing rightward and
+    /// y increasing downward from the top-left character, so the grid uses
+    /// `YAxis::Down`. Lines are not trimmed, and characters for which
+    /// `parse_tile` returns `None` are left empty.
+    pub fn parse<F>(input: &str, mut parse_tile: F) -> Self
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let width = input.lines().map(|line| line.chars().count()).max();
+        let height = input.lines().count() as i64;
+        let mut grid = match width {
+            Some(width) if width > 0 => Self::dense_with_bounds(
+                Bounds {
+                    min: Point { x: 0, y: 0 },
+                    max: Point {
+                        x: width as i64 - 1,
+                        y: height - 1,
+                    },
+                },
+                YAxis::Down,
+            ),
+            _ => Self::dense(YAxis::Down),
+        };
+
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if let Some(tile) = parse_tile(c) {
+                    let point = Point {
+                        x: x as i64,
+                        y: y as i64,
+                    };
+                    grid.insert(point, tile);
+                }
+            }
+        }
+
+        grid
+    }
+
+    pub fn y_axis(&self) -> YAxis {
+        self.y_axis
+    }
+
+    /// The point one step from `point` in `direction`, following the grid's
+    /// convention.
+    pub fn step(&self, point: Point, direction: Direction) -> Point {
+        point + direction.offset(self.y_axis)
+    }
+
+    fn index(bounds: &Bounds, point: &Point) -> usize {
+        (point.y - bounds.min.y) as usize * bounds.width() + (point.x - bounds.min.x) as usize
+    }
+
+    pub fn get(&self, point: &Point) -> Option<&T> {
+        match &self.cells {
+            Cells::Sparse(cells) => cells.get(point),
+            Cells::Dense { bounds, cells, .. } => match bounds {
+                Some(bounds) if bounds.contains(point) => {
+                    cells[Self::index(bounds, point)].as_ref()
+                }
+                _ => None,
+            },
+        }
+    }
+
+    pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
+        match &mut self.cells {
+            Cells::Sparse(cells) => cells.get_mut(point),
+            Cells::Dense { bounds, cells, .. } => match bounds {
+                Some(bounds) if bounds.contains(point) => {
+                    cells[Self::index(bounds, point)].as_mut()
+                }
+                _ => None,
+            },
+        }
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        self.get(point).is_some()
+    }
+
+    /// Sets a tile, returning the one it replaced.
+    pub fn insert(&mut self, point: Point, tile: T) -> Option<T> {
+        match &mut self.cells {
+            Cells::Sparse(cells) => cells.insert(point, tile),
+            Cells::Dense { bounds, cells, len } => {
+                let current = match bounds {
+                    Some(current) => *current,
+                    None => Bounds::around(point),
+                };
+
+                if bounds.is_none() || !current.contains(&point) {
+                    let grown = Self::grow(bounds.as_ref(), point);
+                    let mut resized: Vec<Option<T>> =
+                        (0..grown.width() * grown.height()).map(|_| None).collect();
+                    if let Some(old) = bounds {
+                        for (point, cell) in old.points().zip(cells.drain(..)) {
+                            resized[Self::index(&grown, &point)] = cell;
+                        }
+                    }
+
+                    *bounds = Some(grown);
+                    *cells = resized;
+                }
+
+                let bounds = bounds.as_ref().unwrap();
+                let previous = cells[Self::index(bounds, &point)].replace(tile);
+                if previous.is_none() {
+                    *len += 1;
+                }
+                previous
+            }
+        }
+    }
+
+    // The rectangle to reallocate a dense grid to so that it holds `point`.
+    // Each side that has to move is pushed out by at least the current width
+    // or height, so a run of inserts reallocates only logarithmically often.
+    fn grow(bounds: Option<&Bounds>, point: Point) -> Bounds {
+        let current = match bounds {
+            Some(current) => current,
+            None => return Bounds::around(point),
+        };
+
+        let needed = current.including(point);
+        let (width, height) = (current.width() as i64, current.height() as i64);
+        let push = |needed: i64, current: i64, by: i64| {
+            if needed < current {
+                needed.min(current - by)
+            } else if needed > current {
+                needed.max(current + by)
+            } else {
+                current
+            }
+        };
+
+        Bounds {
+            min: Point {
+                x: push(needed.min.x, current.min.x, width),
+                y: push(needed.min.y, current.min.y, height),
+            },
+            max: Point {
+                x: push(needed.max.x, current.max.x, width),
+                y: push(needed.max.y, current.max.y, height),
+            },
+        }
+    }
+
+    pub fn remove(&mut self, point: &Point) -> Option<T> {
+        match &mut self.cells {
+            Cells::Sparse(cells) => cells.remove(point),
+            Cells::Dense { bounds, cells, len } => match bounds {
+                Some(bounds) if bounds.contains(point) => {
+                    let previous = cells[Self::index(bounds, point)].take();
+                    if previous.is_some() {
+                        *len -= 1;
+                    }
+                    previous
+                }
+                _ => None,
+            },
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.cells {
+            Cells::Sparse(cells) => cells.len(),
+            Cells::Dense { len, .. } => *len,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Every set tile. Dense grids yield them in reading order; sparse grids
+    /// in no particular order.
+    pub fn iter(&self) -> Box<dyn Iterator<Item = (Point, &T)> + '_> {
+        match &self.cells {
+            Cells::Sparse(cells) => Box::new(cells.iter().map(|(&point, tile)| (point, tile))),
+            Cells::Dense { bounds, cells, .. } => Box::new(
+                bounds
+                    .iter()
+                    .flat_map(Bounds::points)
+                    .zip(cells)
+                    .filter_map(|(point, cell)| cell.as_ref().map(|tile| (point, tile))),
+            ),
+        }
+    }
+
+    /// The smallest bounds containing every set tile, or `None` when empty.
+    pub fn bounds(&self) -> Option<Bounds> {
+        let mut points = self.iter().map(|(point, _)| point);
+        let first = points.next()?;
+        Some(points.fold(Bounds::around(first), |bounds, point| {
+            bounds.including(point)
+        }))
+    }
+
+    fn neighbors<'a>(
+        &'a self,
+        point: Point,
+        offsets: &'a [Point],
+    ) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        offsets.iter().filter_map(move |&offset| {
+            let neighbor = point + offset;
+            self.get(&neighbor).map(|tile| (neighbor, tile))
+        })
+    }
+
+    /// The set tiles orthogonally adjacent to `point`.
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors(point, &NEIGHBORS4)
+    }
+
+    /// The set tiles orthogonally or diagonally adjacent to `point`.
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors(point, &NEIGHBORS8)
+    }
+
+    /// Draws the bounding box of the set tiles as lines of text, one
+    /// character per cell, with North at the top. `render_tile` receives
+    /// `None` for empty cells.
+    pub fn render<F>(&self, render_tile: F) -> String
+    where
+        F: Fn(Option<&T>) -> char,
+    {
+        let bounds = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return String::new(),
+        };
+
+        let rows: Vec<i64> = match self.y_axis {
+            YAxis::Down => (bounds.min.y..=bounds.max.y).collect(),
+            YAxis::Up => (bounds.min.y..=bounds.max.y).rev().collect(),
+        };
+
+        rows.into_iter()
+            .map(|y| {
+                (bounds.min.x..=bounds.max.x)
+                    .map(|x| render_tile(self.get(&Point { x, y })))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "\
+#.#
+...
+##.";
+
+    fn walls(grid: &Grid<char>) -> Vec<Point> {
+        let mut walls: Vec<Point> = grid
+            .iter()
+            .filter(|(_, &c)| c == '#')
+            .map(|(point, _)| point)
+            .collect();
+        walls.sort_by_key(|p| (p.y, p.x));
+        walls
+    }
+
+    #[test]
+    fn test_dense_and_sparse_agree() {
+        let dense = Grid::parse(MAP, Some);
+        let mut sparse = Grid::sparse(YAxis::Down);
+        for (point, &c) in dense.iter() {
+            sparse.insert(point, c);
+        }
+
+        assert_eq!(dense.len(), 9);
+        assert_eq!(sparse.len(), 9);
+        assert_eq!(walls(&dense), walls(&sparse));
+        assert_eq!(dense.bounds(), sparse.bounds());
+        assert_eq!(
+            dense.render(|c| *c.unwrap()),
+            sparse.render(|c| *c.unwrap())
+        );
+    }
+
+    #[test]
+    fn test_dense_grows() {
+        let mut grid = Grid::dense(YAxis::Down);
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(Point { x: 2, y: 3 }, 'a');
+        grid.insert(Point { x: -1, y: 5 }, 'b');
+        assert_eq!(grid.insert(Point { x: 2, y: 3 }, 'c'), Some('a'));
+
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.get(&Point { x: 2, y: 3 }), Some(&'c'));
+        assert_eq!(grid.get(&Point { x: 0, y: 4 }), None);
+        assert_eq!(
+            grid.bounds(),
+            Some(Bounds {
+                min: Point { x: -1, y: 3 },
+                max: Point { x: 2, y: 5 },
+            })
+        );
+
+        assert_eq!(grid.remove(&Point { x: -1, y: 5 }), Some('b'));
+        assert_eq!(grid.len(), 1);
+        assert_eq!(grid.bounds(), Some(Bounds::around(Point { x: 2, y: 3 })));
+    }
+
+    #[test]
+    fn test_dense_grows_geometrically() {
+        fn allocated<T>(grid: &Grid<T>) -> Bounds {
+            match grid.cells {
+                Cells::Dense { bounds, .. } => bounds.unwrap(),
+                Cells::Sparse(_) => unreachable!(),
+            }
+        }
+
+        let mut grid = Grid::dense(YAxis::Down);
+        for x in 0..5 {
+            grid.insert(Point { x, y: 0 }, x);
+        }
+        assert_eq!(allocated(&grid).max, Point { x: 7, y: 0 });
+
+        grid.insert(Point { x: 0, y: -1 }, -1);
+        assert_eq!(allocated(&grid).min, Point { x: 0, y: -1 });
+        assert_eq!(grid.bounds().unwrap().max, Point { x: 4, y: 0 });
+
+        let parsed = Grid::parse("#.\n.#..", Some);
+        assert_eq!(allocated(&parsed).max, Point { x: 3, y: 1 });
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::parse(MAP, |c| if c == '#' { Some(()) } else { None });
+        let center = Point { x: 1, y: 1 };
+
+        let orthogonal: Vec<Point> = grid.neighbors4(center).map(|(p, _)| p).collect();
+        assert_eq!(orthogonal, vec![Point { x: 1, y: 2 }]);
+
+        let all: Vec<Point> = grid.neighbors8(center).map(|(p, _)| p).collect();
+        assert_eq!(
+            all,
+            vec![
+                Point { x: 0, y: 0 },
+                Point { x: 2, y: 0 },
+                Point { x: 0, y: 2 },
+                Point { x: 1, y: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_orientation() {
+        let parsed = Grid::parse(MAP, |c| if c == '#' { Some(()) } else { None });
+        let mut flipped = Grid::sparse(YAxis::Up);
+        for (point, _) in parsed.iter() {
+            flipped.insert(point, ());
+        }
+
+        let render = |grid: &Grid<()>| grid.render(|c| if c.is_some() { '#' } else { '.' });
+        assert_eq!(render(&parsed), MAP);
+        assert_eq!(render(&flipped), "##.\n...\n#.#");
+        assert_eq!(render(&Grid::sparse(YAxis::Down)), "");
+
+        // North is always towards the top row when drawn.
+        let corner = Point { x: 0, y: 2 };
+        assert_eq!(parsed.step(corner, Direction::North), Point { x: 0, y: 1 });
+        assert_eq!(flipped.step(corner, Direction::North), Point { x: 0, y: 3 });
+    }
+}
@@ -0,0 +1,144 @@
+//! Number theory and geometry helpers.
+
+use crate::common::Point;
+use std::cmp::Ordering;
+
+/// Greatest common divisor, always non-negative.
+///
+/// ```
+/// use aoc2019::common::math::{gcd, lcm};
+///
+/// assert_eq!(gcd(-12, 18), 6);
+/// assert_eq!(lcm(4, 6), 12);
+/// ```
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+
+    a
+}
+
+/// Least common multiple, always non-negative.
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+
+    (a / gcd(a, b) * b).abs()
+}
+
+/// Multiplicative inverse of `value` modulo `modulus`, or `None` when they
+/// are not coprime.
+///
+/// ```
+/// use aoc2019::common::math::mod_inverse;
+///
+/// assert_eq!(mod_inverse(3, 10), Some(7));
+/// assert_eq!(mod_inverse(4, 10), None);
+/// ```
+pub fn mod_inverse(value: i128, modulus: i128) -> Option<i128> {
+    let (mut old_r, mut r) = (value.rem_euclid(modulus), modulus);
+    let (mut old_s, mut s) = (1, 0);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        let next_r = old_r - quotient * r;
+        old_r = r;
+        r = next_r;
+        let next_s = old_s - quotient * s;
+        old_s = s;
+        s = next_s;
+    }
+
+    if old_r == 1 {
+        Some(old_s.rem_euclid(modulus))
+    } else {
+        None
+    }
+}
+
+/// Orders direction vectors by their clockwise angle from straight up, using
+/// screen coordinates (y increases downward). Comparison is exact: vectors are
+/// split into the right and left half-planes, then ordered by cross product.
+///
+/// ```
+/// use aoc2019::common::math::compare_clockwise;
+/// use aoc2019::common::Point;
+///
+/// let mut directions = vec![Point { x: -1, y: 0 }, Point { x: 1, y: 0 }, Point { x: 0, y: -1 }];
+/// directions.sort_by(compare_clockwise);
+/// assert_eq!(directions, [Point { x: 0, y: -1 }, Point { x: 1, y: 0 }, Point { x: -1, y: 0 }]);
+/// ```
+pub fn compare_clockwise(a: &Point, b: &Point) -> Ordering {
+    fn half(p: &Point) -> u8 {
+        if p.x > 0 || (p.x == 0 && p.y < 0) {
+            0
+        } else {
+            1
+        }
+    }
+
+    half(a).cmp(&half(b)).then_with(|| {
+        let cross = a.x * b.y - a.y * b.x;
+        0.cmp(&cross)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-4, 6), 2);
+        assert_eq!(gcd(0, -5), 5);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(18, 28), 252);
+        assert_eq!(lcm(0, 3), 0);
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 7), Some(5));
+        assert_eq!(mod_inverse(-3, 7), Some(2));
+        assert_eq!(mod_inverse(4, 8), None);
+    }
+
+    #[test]
+    fn test_compare_clockwise() {
+        let mut points = vec![
+            Point { x: -1, y: -1 },
+            Point { x: 0, y: 1 },
+            Point { x: -1, y: 0 },
+            Point { x: 1, y: 1 },
+            Point { x: 0, y: -1 },
+            Point { x: 1, y: 0 },
+            Point { x: -1, y: 1 },
+            Point { x: 1, y: -1 },
+        ];
+        points.sort_by(compare_clockwise);
+
+        assert_eq!(
+            points,
+            vec![
+                Point { x: 0, y: -1 },
+                Point { x: 1, y: -1 },
+                Point { x: 1, y: 0 },
+                Point { x: 1, y: 1 },
+                Point { x: 0, y: 1 },
+                Point { x: -1, y: 1 },
+                Point { x: -1, y: 0 },
+                Point { x: -1, y: -1 },
+            ]
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Locating the arcade cabinet's data in its program's memory, and patching
+//! it.
+//!
+//! The cabinet keeps its playfield as a tile map, one tile ID per cell row by
+//! row, and draws the first screen from it. Matching the memory image against
+//! that screen finds the map. The table of points for each cell follows it,
+//! one positive entry per cell. Patching the map before the game starts
+//! changes the playfield: turning the paddle's row into wall means the ball
+//! can never be lost, so the game plays itself.
+
+use crate::common::care_package::{Game, Tile};
+use crate::common::grid::Bounds;
+use crate::common::Point;
+use anyhow::{anyhow, Result};
+use std::ops::Range;
+
+/// Where a cabinet program keeps its playfield.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemoryMap {
+    /// The cells of the playfield.
+    pub bounds: Bounds,
+    /// The address of the tile map's first cell, the top-left one.
+    pub tiles: usize,
+    /// The address of the score table, if one follows the tile map.
+    pub scores: Option<usize>,
+    /// Where the paddle starts.
+    pub paddle: Point,
+}
+
+impl MemoryMap {
+    /// Loads the program, draws its first screen and searches its memory for
+    /// a tile map matching it. The paddle and ball may be drawn over empty
+    /// cells of the map rather than stored in it.
+    pub fn analyze(source: &str) -> Result<Self> {
+        let game = Game::new(source, false)?;
+        let screen = &game.screen;
+        let bounds = screen
+            .bounds()
+            .ok_or_else(|| anyhow!("The program draws nothing"))?;
+
+        let expected: Vec<i64> = bounds.points().map(|p| screen.tile_id(&p)).collect();
+        let matches = |cells: &[i64]| {
+            cells.iter().zip(&expected).all(|(&cell, &tile)| {
+                let moving = tile == Tile::Paddle.id() || tile == Tile::Ball.id();
+                cell == tile || (cell == Tile::Empty.id() && moving)
+            })
+        };
+
+        let memory = game.memory();
+        let tiles = memory
+            .windows(expected.len())
+            .position(matches)
+            .ok_or_else(|| anyhow!("No tile map matches the first screen"))?;
+
+        let end = tiles + expected.len();
+        let scores = memory
+            .get(end..end + expected.len())
+            .filter(|table| table.iter().all(|&points| points > 0))
+            .map(|_| end);
+
+        Ok(Self {
+            bounds,
+            tiles,
+            scores,
+            paddle: screen.paddle,
+        })
+    }
+
+    /// The addresses the tile map occupies.
+    pub fn tile_map(&self) -> Range<usize> {
+        self.tiles..self.tiles + self.bounds.width() * self.bounds.height()
+    }
+
+    /// The address holding the tile at `point`, if it is on the playfield.
+    pub fn address(&self, point: &Point) -> Option<usize> {
+        if !self.bounds.contains(point) {
+            return None;
+        }
+
+        let row = (point.y - self.bounds.min.y) as usize;
+        let column = (point.x - self.bounds.min.x) as usize;
+        Some(self.tiles + row * self.bounds.width() + column)
+    }
+
+    /// Patches for [`Game::with_patches`] that turn the paddle's whole row
+    /// into wall.
+    pub fn wall_paddle_row(&self) -> Vec<(usize, i64)> {
+        let y = self.paddle.y;
+        (self.bounds.min.x..=self.bounds.max.x)
+            .filter_map(|x| self.address(&Point { x, y }))
+            .map(|address| (address, Tile::Wall.id()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Draws a 5x4 playfield from a tile map at address 51, reading it with
+    // relative addressing, then shows a score of 0. The score table follows
+    // the map. It starts with a no-op that free play's poke leaves harmless.
+    const PROGRAM: &str = "1,0,0,0,109,51,1101,0,0,301,1101,0,0,300,4,300,4,301,204,0,109,1,1001,\
+        300,1,300,1007,300,5,302,1005,302,14,1001,301,1,301,1007,301,4,302,1005,\
+        302,10,104,-1,104,0,104,0,99,\
+        1,1,1,1,1,\
+        1,2,2,2,1,\
+        1,0,4,0,1,\
+        1,0,3,0,1,\
+        1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20";
+
+    #[test]
+    fn test_analyze() -> Result<()> {
+        let map = MemoryMap::analyze(PROGRAM)?;
+        assert_eq!(map.tiles, 51);
+        assert_eq!(map.tile_map(), 51..71);
+        assert_eq!(map.scores, Some(71));
+        assert_eq!(map.paddle, Point { x: 2, y: 3 });
+
+        assert_eq!(map.address(&Point { x: 2, y: 1 }), Some(58));
+        assert_eq!(map.address(&Point { x: 5, y: 0 }), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_wall_paddle_row() -> Result<()> {
+        let map = MemoryMap::analyze(PROGRAM)?;
+        let patches = map.wall_paddle_row();
+        assert_eq!(
+            patches,
+            (66..71).map(|a| (a, Tile::Wall.id())).collect::<Vec<_>>()
+        );
+
+        let game = Game::with_patches(PROGRAM, true, &patches)?;
+        assert_eq!(game.screen.to_string(), "█████\n█░░░█\n█ ■ █\n█████");
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_tile_map() {
+        // Two blocks drawn from immediate values, which memory never holds
+        // side by side.
+        let program = "104,0,104,0,104,2,104,1,104,0,104,2,99";
+        assert!(MemoryMap::analyze(program).is_err());
+        assert!(MemoryMap::analyze("99").is_err());
+    }
+}
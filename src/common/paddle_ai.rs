@@ -0,0 +1,286 @@
+//! Strategies that play the care package game to the end.
+//!
+//! The ball's path does not depend on the paddle until it lands, so a fork of
+//! the game played with the joystick left neutral shows where and when the
+//! ball will next come down. [`Tracking`] ignores this and chases the ball
+//! one frame at a time; [`Predictive`] heads straight for the next landing;
+//! [`Planned`] also knows how many frames it has to get there, and sends all
+//! of them in a single step.
+
+use crate::common::care_package::{autopilot, Game};
+use crate::common::Direction;
+use anyhow::{anyhow, Result};
+use std::time::{Duration, Instant};
+
+// Forecasts give up after this many frames without a landing.
+const FORECAST_LIMIT: usize = 100_000;
+
+/// Chooses the joystick inputs for the next step of a game.
+pub trait Strategy {
+    fn name(&self) -> &'static str;
+
+    /// The moves to make next, all sent to the game in one run.
+    fn plan(&mut self, game: &Game) -> Result<Vec<Option<Direction>>>;
+}
+
+/// The original autopilot: every frame, tilt towards the ball's current x.
+pub struct Tracking;
+
+impl Strategy for Tracking {
+    fn name(&self) -> &'static str {
+        "tracking"
+    }
+
+    fn plan(&mut self, game: &Game) -> Result<Vec<Option<Direction>>> {
+        Ok(vec![autopilot(&game.screen)])
+    }
+}
+
+/// Leaves the joystick neutral, for cabinets patched to play themselves.
+pub struct Idle;
+
+impl Strategy for Idle {
+    fn name(&self) -> &'static str {
+        "idle"
+    }
+
+    fn plan(&mut self, _game: &Game) -> Result<Vec<Option<Direction>>> {
+        Ok(vec![None])
+    }
+}
+
+/// Forecasts the next landing whenever the last one has passed, then tilts
+/// towards it one frame at a time.
+#[derive(Default)]
+pub struct Predictive {
+    forecast: Option<Forecast>,
+}
+
+impl Strategy for Predictive {
+    fn name(&self) -> &'static str {
+        "predictive"
+    }
+
+    fn plan(&mut self, game: &Game) -> Result<Vec<Option<Direction>>> {
+        let forecast = match self.forecast {
+            Some(forecast) if forecast.frames > 0 => forecast,
+            _ => Forecast::new(game)?,
+        };
+
+        self.forecast = Some(Forecast {
+            frames: forecast.frames - 1,
+            ..forecast
+        });
+        Ok(vec![forecast.landing.and_then(|x| towards(game, x))])
+    }
+}
+
+/// Plans every frame up to the next landing at once, tilting only as far as
+/// needed. This takes one step per landing and the fewest tilts possible.
+pub struct Planned;
+
+impl Strategy for Planned {
+    fn name(&self) -> &'static str {
+        "planned"
+    }
+
+    fn plan(&mut self, game: &Game) -> Result<Vec<Option<Direction>>> {
+        let forecast = Forecast::new(game)?;
+        let mut moves = vec![None; forecast.frames];
+
+        if let Some(x) = forecast.landing {
+            let distance = (x - game.screen.paddle.x).unsigned_abs() as usize;
+            if distance > moves.len() {
+                return Err(anyhow!("The paddle cannot reach x = {} in time", x));
+            }
+
+            let tilt = towards(game, x);
+            moves[..distance].iter_mut().for_each(|m| *m = tilt);
+        }
+
+        Ok(moves)
+    }
+}
+
+fn towards(game: &Game, x: i64) -> Option<Direction> {
+    let paddle = game.screen.paddle.x;
+    if paddle < x {
+        Some(Direction::East)
+    } else if paddle > x {
+        Some(Direction::West)
+    } else {
+        None
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Forecast {
+    // Frames until the ball is just above the paddle row, or until the game
+    // finishes when there is no landing.
+    frames: usize,
+    landing: Option<i64>,
+}
+
+impl Forecast {
+    fn new(game: &Game) -> Result<Self> {
+        let landing_row = game.screen.paddle.y - 1;
+        let mut fork = game.fork();
+
+        for frames in 1..=FORECAST_LIMIT {
+            // The fork's paddle may miss the ball, ending its game, so the
+            // landing is checked first.
+            fork.do_move(None)?;
+            let screen = &fork.screen;
+            if screen.ball.y == landing_row {
+                return Ok(Self {
+                    frames,
+                    landing: Some(screen.ball.x),
+                });
+            }
+
+            if screen.remaining_blocks == 0 || fork.is_over() {
+                return Ok(Self {
+                    frames,
+                    landing: None,
+                });
+            }
+        }
+
+        Err(anyhow!(
+            "The ball did not land within {} frames",
+            FORECAST_LIMIT
+        ))
+    }
+}
+
+/// How a strategy fared over a whole game.
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub strategy: &'static str,
+    pub score: u64,
+    /// Plans made, each sent to the program in one run.
+    pub steps: usize,
+    /// Joystick inputs, one per frame.
+    pub moves: usize,
+    /// Inputs that tilted the joystick.
+    pub tilts: usize,
+    /// Time spent playing, after the game was loaded.
+    pub elapsed: Duration,
+}
+
+impl Report {
+    /// Frames played per second.
+    pub fn throughput(&self) -> f64 {
+        self.moves as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+/// Plays a new game on free play until every block is broken.
+pub fn play<S: Strategy>(source: &str, strategy: &mut S) -> Result<Report> {
+    play_game(Game::new(source, true)?, strategy)
+}
+
+/// Plays an already loaded game, such as a patched one, until every block is
+/// broken.
+pub fn play_game<S: Strategy>(mut game: Game, strategy: &mut S) -> Result<Report> {
+    let start = Instant::now();
+    let (mut steps, mut tilts) = (0, 0);
+
+    while game.screen.remaining_blocks > 0 {
+        if game.is_over() {
+            return Err(anyhow!(
+                "{} lost the ball with {} blocks left",
+                strategy.name(),
+                game.screen.remaining_blocks
+            ));
+        }
+
+        let plan = strategy.plan(&game)?;
+        if plan.is_empty() {
+            return Err(anyhow!("{} made an empty plan", strategy.name()));
+        }
+
+        steps += 1;
+        tilts += plan.iter().filter(|tilt| tilt.is_some()).count();
+        game.do_moves(&plan)?;
+    }
+
+    Ok(Report {
+        strategy: strategy.name(),
+        score: game.screen.score,
+        steps,
+        moves: game.moves(),
+        tilts,
+        elapsed: start.elapsed(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A small breakout: blocks at x = 1, 3 and 5 on row 0, a ball bouncing
+    // diagonally between x = 1 and 5 and rows 1 and 4, and a paddle on row 5
+    // that starts at x = 3. The ball bounces if the paddle is within one
+    // column of it, scoring a point, and otherwise the program halts with the
+    // ball drawn where it was missed. It starts with a no-op that free play's
+    // poke leaves harmless.
+    const PROGRAM: &str =
+        "1,0,0,0,104,1,104,0,104,2,104,3,104,0,104,2,104,5,104,0,104,2,104,1,104,\
+        1,104,4,104,3,104,5,104,3,1101,1,0,200,1101,1,0,201,1101,1,0,202,1101,1,\
+        0,203,1101,3,0,204,3,207,4,204,104,5,104,0,1,204,207,204,4,204,104,5,104,\
+        3,4,200,4,201,104,0,1,200,202,200,1,201,203,201,1008,200,1,206,1006,206,\
+        97,1002,202,-1,202,1008,200,5,206,1006,206,108,1002,202,-1,202,1008,201,\
+        1,206,1006,206,125,4,200,104,0,104,0,1101,1,0,203,4,200,4,201,104,4,1008,\
+        201,4,206,1006,206,54,1002,200,-1,206,1,206,204,206,2,206,206,206,1007,\
+        206,2,206,1006,206,174,1101,-1,0,203,1001,205,1,205,104,-1,104,0,4,205,\
+        1105,1,54,99";
+
+    #[test]
+    fn test_forecast() -> Result<()> {
+        let game = Game::new(PROGRAM, true)?;
+        let forecast = Forecast::new(&game)?;
+        assert_eq!(
+            forecast,
+            Forecast {
+                frames: 3,
+                landing: Some(4),
+            }
+        );
+
+        // Forecasting leaves the game untouched.
+        assert_eq!(game.moves(), 0);
+        assert_eq!(game.screen.ball.x, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_strategies_agree() -> Result<()> {
+        let tracking = play(PROGRAM, &mut Tracking)?;
+        let predictive = play(PROGRAM, &mut Predictive::default())?;
+        let planned = play(PROGRAM, &mut Planned)?;
+
+        // The ball's path is fixed, so every strategy takes as long.
+        for report in &[&predictive, &planned] {
+            assert_eq!(report.score, tracking.score);
+            assert_eq!(report.moves, tracking.moves);
+        }
+
+        assert_eq!(tracking.steps, tracking.moves);
+        assert_eq!(predictive.steps, predictive.moves);
+        assert!(planned.steps < predictive.steps);
+
+        assert!(predictive.tilts < tracking.tilts);
+        assert_eq!(planned.tilts, predictive.tilts);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unreachable_landing() -> Result<()> {
+        // With the paddle moved away, the ball lands before it can get back.
+        let mut game = Game::new(PROGRAM, true)?;
+        game.do_moves(&[Some(Direction::West), Some(Direction::West)])?;
+        assert!(Planned.plan(&game).is_err());
+        Ok(())
+    }
+}
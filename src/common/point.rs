@@ -0,0 +1,311 @@
+//! Integer points and offsets in two and three dimensions.
+
+use anyhow::{anyhow, Context, Error};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// The coordinate convention of a 2D space: which way increasing y points.
+///
+/// Direction offsets and grid rendering both depend on it, so a grid records
+/// its convention and moves on it go through [`Direction::offset`].
+///
+/// [`Direction::offset`]: crate::common::Direction::offset
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum YAxis {
+    /// Screen coordinates, as in puzzle text: North is y - 1 and the smallest
+    /// y is drawn as the top row.
+    Down,
+    /// Cartesian coordinates: North is y + 1 and the largest y is drawn as the
+    /// top row.
+    Up,
+}
+
+/// A position or offset on a 2D grid.
+///
+/// Points order by reading order, row by row and then by column, and are
+/// written and parsed as `x,y`.
+///
+/// ```
+/// use aoc2019::common::Point;
+///
+/// let p: Point = "3,-4".parse()?;
+/// assert_eq!(p.manhattan_length(), 7);
+/// assert_eq!(-p * 2, Point { x: -6, y: 8 });
+/// assert_eq!(p.rotate_left().to_string(), "4,3");
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone, Default)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A position or offset in 3D space, with the same operations as [`Point`].
+/// Points order by z, then y, then x.
+#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone, Default)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+// Implements the arithmetic, distances and text format shared by both point
+// types, coordinate by coordinate.
+macro_rules! impl_point {
+    ($point:ident { $($field:ident),+ }) => {
+        impl Add for $point {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl Sub for $point {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl Neg for $point {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl Mul<i64> for $point {
+            type Output = Self;
+
+            fn mul(self, scalar: i64) -> Self {
+                Self { $($field: self.$field * scalar),+ }
+            }
+        }
+
+        impl AddAssign for $point {
+            fn add_assign(&mut self, other: Self) {
+                $(self.$field += other.$field;)+
+            }
+        }
+
+        impl SubAssign for $point {
+            fn sub_assign(&mut self, other: Self) {
+                $(self.$field -= other.$field;)+
+            }
+        }
+
+        impl PartialOrd for $point {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl $point {
+            /// Sum of the absolute coordinates: the taxicab distance from the
+            /// origin.
+            pub fn manhattan_length(&self) -> i64 {
+                0 $(+ self.$field.abs())+
+            }
+
+            /// Largest absolute coordinate: the number of king moves from the
+            /// origin.
+            pub fn chebyshev_length(&self) -> i64 {
+                0 $(.max(self.$field.abs()))+
+            }
+
+            pub fn manhattan_distance(&self, other: &Self) -> i64 {
+                (*self - *other).manhattan_length()
+            }
+
+            pub fn chebyshev_distance(&self, other: &Self) -> i64 {
+                (*self - *other).chebyshev_length()
+            }
+        }
+
+        impl fmt::Display for $point {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let coordinates = [$(self.$field.to_string()),+];
+                write!(f, "{}", coordinates.join(","))
+            }
+        }
+
+        impl FromStr for $point {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self, Error> {
+                let names = [$(stringify!($field)),+];
+                let parts: Vec<&str> = s.trim().split(',').collect();
+                if parts.len() != names.len() {
+                    return Err(anyhow!(
+                        "Expected {} comma-separated coordinates, got {:?}",
+                        names.len(),
+                        s
+                    ));
+                }
+
+                let mut parts = parts.into_iter();
+                Ok(Self {
+                    $($field: parts
+                        .next()
+                        .unwrap()
+                        .trim()
+                        .parse()
+                        .with_context(|| format!("Invalid {} coordinate in {:?}", stringify!($field), s))?),+
+                })
+            }
+        }
+    };
+}
+
+impl_point!(Point { x, y });
+impl_point!(Point3 { x, y, z });
+
+impl Ord for Point {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.y, self.x).cmp(&(other.y, other.x))
+    }
+}
+
+impl Ord for Point3 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.z, self.y, self.x).cmp(&(other.z, other.y, other.x))
+    }
+}
+
+// Rotations treat y as pointing up, so a left turn is counterclockwise. With
+// `YAxis::Down` the same turn is clockwise on screen.
+impl Point {
+    pub fn rotate_left(self) -> Self {
+        Self {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+
+    pub fn rotate_right(self) -> Self {
+        Self {
+            x: self.y,
+            y: -self.x,
+        }
+    }
+
+    /// Rotates by a number of quarter turns, counterclockwise when positive.
+    pub fn rotate(self, quarter_turns: i64) -> Self {
+        match quarter_turns.rem_euclid(4) {
+            0 => self,
+            1 => self.rotate_left(),
+            2 => -self,
+            _ => self.rotate_right(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    const P: Point = Point { x: 3, y: -4 };
+    const Q: Point = Point { x: -1, y: 2 };
+    const P3: Point3 = Point3 { x: 1, y: -2, z: 3 };
+    const Q3: Point3 = Point3 { x: 4, y: 0, z: -5 };
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(P + Q, Point { x: 2, y: -2 });
+        assert_eq!(P - Q, Point { x: 4, y: -6 });
+        assert_eq!(-P, Point { x: -3, y: 4 });
+        assert_eq!(P * 3, Point { x: 9, y: -12 });
+
+        assert_eq!(P3 + Q3, Point3 { x: 5, y: -2, z: -2 });
+        assert_eq!(P3 - Q3, Point3 { x: -3, y: -2, z: 8 });
+        assert_eq!(-P3, Point3 { x: -1, y: 2, z: -3 });
+        assert_eq!(P3 * -2, Point3 { x: -2, y: 4, z: -6 });
+    }
+
+    #[test]
+    fn test_assign() {
+        let mut p = P;
+        p += Q;
+        assert_eq!(p, P + Q);
+        p -= Q;
+        assert_eq!(p, P);
+
+        let mut p3 = P3;
+        p3 += Q3;
+        assert_eq!(p3, P3 + Q3);
+        p3 -= Q3;
+        assert_eq!(p3, P3);
+    }
+
+    #[test]
+    fn test_distances() {
+        assert_eq!(P.manhattan_length(), 7);
+        assert_eq!(P.chebyshev_length(), 4);
+        assert_eq!(P.manhattan_distance(&Q), 10);
+        assert_eq!(P.chebyshev_distance(&Q), 6);
+        assert_eq!(Point::default().manhattan_length(), 0);
+
+        assert_eq!(P3.manhattan_length(), 6);
+        assert_eq!(P3.chebyshev_length(), 3);
+        assert_eq!(P3.manhattan_distance(&Q3), 13);
+        assert_eq!(P3.chebyshev_distance(&Q3), 8);
+    }
+
+    #[test]
+    fn test_rotations() {
+        let east = Point { x: 1, y: 0 };
+        let north = Point { x: 0, y: 1 };
+
+        assert_eq!(east.rotate_left(), north);
+        assert_eq!(north.rotate_right(), east);
+        assert_eq!(P.rotate_left().rotate_right(), P);
+        assert_eq!(P.rotate(2), -P);
+        assert_eq!(P.rotate(-1), P.rotate_right());
+        assert_eq!(P.rotate(5), P.rotate_left());
+        assert_eq!(P.rotate(4), P);
+    }
+
+    #[test]
+    fn test_reading_order() {
+        let mut points = vec![
+            Point { x: 2, y: 1 },
+            Point { x: 5, y: 0 },
+            Point { x: 0, y: 1 },
+        ];
+        points.sort();
+        assert_eq!(
+            points,
+            [
+                Point { x: 5, y: 0 },
+                Point { x: 0, y: 1 },
+                Point { x: 2, y: 1 },
+            ]
+        );
+
+        assert!(Point3 { x: 9, y: 9, z: 0 } < Point3 { x: 0, y: 0, z: 1 });
+        assert!(Point3 { x: 9, y: 0, z: 1 } < Point3 { x: 0, y: 1, z: 1 });
+        assert!(Point3 { x: 0, y: 1, z: 1 } < Point3 { x: 1, y: 1, z: 1 });
+    }
+
+    #[test]
+    fn test_text_round_trip() -> Result<()> {
+        assert_eq!(P.to_string(), "3,-4");
+        assert_eq!("3,-4".parse::<Point>()?, P);
+        assert_eq!(" -1, 2 ".parse::<Point>()?, Q);
+
+        assert_eq!(P3.to_string(), "1,-2,3");
+        assert_eq!(P3.to_string().parse::<Point3>()?, P3);
+
+        assert!("1".parse::<Point>().is_err());
+        assert!("1,2,3".parse::<Point>().is_err());
+        assert!("1,y".parse::<Point>().is_err());
+        assert!("1,2".parse::<Point3>().is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,318 @@
+//! Recording and playback of care package games.
+//!
+//! A [`Replay`] is recorded by [`Game::start_recording`] and lists every
+//! joystick input along with the score and block count that followed it. As
+//! text, it is a header saying whether the game was on free play, then one
+//! line per move with the tilt (`<`, `>` or `.` for neutral), score and
+//! remaining blocks:
+//!
+//! ```text
+//! free-play true
+//! > 0 3
+//! . 10 2
+//! ```
+//!
+//! Lines starting with `#` are comments.
+
+use crate::common::care_package::{Game, Joystick, Screen};
+use crate::common::grid::Bounds;
+use crate::common::Direction;
+use anyhow::{anyhow, Context, Error, Result};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// One joystick input and the state of the game after it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Move {
+    pub(crate) joystick: Joystick,
+    pub score: u64,
+    pub remaining_blocks: usize,
+}
+
+impl Move {
+    /// The tilt made: west, east, or `None` for neutral.
+    pub fn tilt(&self) -> Option<Direction> {
+        self.joystick.tilt()
+    }
+}
+
+/// The moves of a game, played from the start.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replay {
+    pub free_play: bool,
+    pub moves: Vec<Move>,
+}
+
+impl Replay {
+    /// Plays the moves against `source` and checks that every move leads to
+    /// the recorded score and block count. Returns each screen shown, from
+    /// the initial one to the one after the last move.
+    pub fn play_back(&self, source: &str) -> Result<Vec<Screen>> {
+        let mut game = Game::new(source, self.free_play)?;
+        let mut frames = vec![game.screen.clone()];
+
+        for (idx, expected) in self.moves.iter().enumerate() {
+            if game.is_over() {
+                return Err(anyhow!(
+                    "Game ended after {} of {} moves",
+                    idx,
+                    self.moves.len()
+                ));
+            }
+
+            let screen = game.do_move(expected.tilt())?;
+            if (screen.score, screen.remaining_blocks)
+                != (expected.score, expected.remaining_blocks)
+            {
+                return Err(anyhow!(
+                    "Move {} diverged: expected score {} with {} blocks, got {} with {}",
+                    idx + 1,
+                    expected.score,
+                    expected.remaining_blocks,
+                    screen.score,
+                    screen.remaining_blocks
+                ));
+            }
+
+            frames.push(screen.clone());
+        }
+
+        Ok(frames)
+    }
+}
+
+const TILTS: [Joystick; 3] = [Joystick::Left, Joystick::Neutral, Joystick::Right];
+
+// The symbol for a joystick position, shared by writing and parsing.
+fn symbol(tilt: Joystick) -> &'static str {
+    match tilt {
+        Joystick::Left => "<",
+        Joystick::Neutral => ".",
+        Joystick::Right => ">",
+    }
+}
+
+impl fmt::Display for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "free-play {}", self.free_play)?;
+        for m in &self.moves {
+            writeln!(
+                f,
+                "{} {} {}",
+                symbol(m.joystick),
+                m.score,
+                m.remaining_blocks
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Replay {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut lines = s
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+        let free_play = match lines.next() {
+            Some((_, "free-play true")) => true,
+            Some((_, "free-play false")) => false,
+            _ => return Err(anyhow!("Replay must start with a free-play header")),
+        };
+
+        let moves = lines
+            .map(|(number, line)| {
+                parse_move(line).with_context(|| format!("Invalid move on line {}", number))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { free_play, moves })
+    }
+}
+
+fn parse_move(line: &str) -> Result<Move> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() != 3 {
+        return Err(anyhow!("Expected a tilt, score and block count"));
+    }
+
+    let joystick = TILTS
+        .iter()
+        .copied()
+        .find(|&joystick| symbol(joystick) == fields[0])
+        .ok_or_else(|| anyhow!("Unknown tilt {:?}", fields[0]))?;
+
+    Ok(Move {
+        joystick,
+        score: fields[1].parse()?,
+        remaining_blocks: fields[2].parse()?,
+    })
+}
+
+/// The smallest bounds containing every frame, so that exported frames all
+/// have the same size.
+fn common_bounds(frames: &[Screen]) -> Option<Bounds> {
+    frames
+        .iter()
+        .filter_map(Screen::bounds)
+        .reduce(|all, bounds| all.including(bounds.min).including(bounds.max))
+}
+
+/// Writes the frames one after another as text, each under a line giving its
+/// number, score and remaining blocks.
+pub fn write_text<W: Write>(frames: &[Screen], out: &mut W) -> Result<()> {
+    for (idx, screen) in frames.iter().enumerate() {
+        writeln!(
+            out,
+            "Frame {}  Score: {}  Blocks: {}",
+            idx, screen.score, screen.remaining_blocks
+        )?;
+        writeln!(out, "{}", screen)?;
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// Writes each frame to `dir` as `frame_NNNN.ppm`, creating the directory if
+/// needed. Tiles are drawn `scale` pixels wide. Returns the number of images.
+pub fn write_ppm_frames(frames: &[Screen], dir: &Path, scale: usize) -> Result<usize> {
+    let bounds = match common_bounds(frames) {
+        Some(bounds) => bounds,
+        None => return Ok(0),
+    };
+
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Unable to create directory {}", dir.display()))?;
+
+    for (idx, screen) in frames.iter().enumerate() {
+        let path = dir.join(format!("frame_{:04}.ppm", idx));
+        let file =
+            File::create(&path).with_context(|| format!("Unable to create {}", path.display()))?;
+
+        let mut out = BufWriter::new(file);
+        screen.write_ppm(bounds, scale, &mut out)?;
+        out.flush()?;
+    }
+
+    Ok(frames.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Draws a wall and two blocks on row 0, then each move reads the
+    // joystick into the paddle x, draws the paddle on row 1 and clears the
+    // block above it for 10 points. Halts once x = 2.
+    const PROGRAM: &str = "104,0,104,0,104,1,104,1,104,0,104,2,104,2,104,0,104,2,\
+                           3,101,1,100,101,100,\
+                           4,100,104,1,104,3,\
+                           4,100,104,0,104,0,\
+                           1001,103,10,103,104,-1,104,0,4,103,\
+                           1008,100,2,102,1005,102,56,1105,1,18,99";
+
+    const EAST: Option<Direction> = Some(Direction::East);
+
+    fn record(tilts: &[Option<Direction>]) -> Result<Replay> {
+        let mut game = Game::new(PROGRAM, false)?;
+        game.start_recording()?;
+        for &tilt in tilts {
+            game.do_move(tilt)?;
+        }
+
+        Ok(game.replay().unwrap().clone())
+    }
+
+    #[test]
+    fn test_record_and_play_back() -> Result<()> {
+        let replay = record(&[EAST, None, EAST])?;
+        assert_eq!(
+            replay.to_string(),
+            "free-play false\n> 10 1\n. 20 1\n> 30 0\n"
+        );
+
+        let frames = replay.play_back(PROGRAM)?;
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[0].remaining_blocks, 2);
+        assert_eq!(frames[3].to_string(), "█  \n ▄▄");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        let replay: Replay = "# a comment\nfree-play false\n\n> 10 1\n. 20 1\n".parse()?;
+        assert_eq!(replay, record(&[EAST, None])?);
+
+        assert!("> 10 1".parse::<Replay>().is_err());
+        assert!("free-play true\n^ 10 1".parse::<Replay>().is_err());
+        assert!("free-play true\n> 10".parse::<Replay>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_every_tilt_round_trips() -> Result<()> {
+        let replay = Replay {
+            free_play: true,
+            moves: TILTS
+                .iter()
+                .map(|&joystick| Move {
+                    joystick,
+                    score: 1,
+                    remaining_blocks: 2,
+                })
+                .collect(),
+        };
+
+        assert_eq!(replay.to_string().parse::<Replay>()?, replay);
+        Ok(())
+    }
+
+    #[test]
+    fn test_divergence() -> Result<()> {
+        let mut replay = record(&[EAST, None])?;
+        replay.moves[1].score = 99;
+        assert!(replay.play_back(PROGRAM).is_err());
+
+        // Moves past the end of the game cannot be replayed either.
+        let mut replay = record(&[EAST, EAST])?;
+        replay.moves.push(replay.moves[0].clone());
+        assert!(replay.play_back(PROGRAM).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_recording_starts_with_the_game() -> Result<()> {
+        let mut game = Game::new(PROGRAM, false)?;
+        game.do_move(None)?;
+        assert!(game.start_recording().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_exports() -> Result<()> {
+        let frames = record(&[EAST, None])?.play_back(PROGRAM)?;
+
+        let mut text = vec![];
+        write_text(&frames, &mut text)?;
+        let text = String::from_utf8(text)?;
+        assert!(text.starts_with("Frame 0  Score: 0  Blocks: 2\n█░░\n\n"));
+        assert_eq!(text.matches("Frame").count(), 3);
+
+        // Every frame is sized to fit the largest one.
+        let mut image = vec![];
+        let bounds = common_bounds(&frames).unwrap();
+        frames[0].write_ppm(bounds, 2, &mut image)?;
+        assert!(image.starts_with(b"P6\n6 4\n255\n"));
+        assert_eq!(image.len(), "P6\n6 4\n255\n".len() + 6 * 4 * 3);
+        Ok(())
+    }
+}
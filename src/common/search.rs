@@ -0,0 +1,335 @@
+//! Shortest-path searches over implicit graphs.
+//!
+//! Graphs are described by a start node and a closure listing each node's
+//! successors, so nodes can be grid points, robot states, or anything else
+//! that is hashable. Each search runs until `is_goal` accepts a node or every
+//! reachable node has been visited.
+//!
+//! ```
+//! use aoc2019::common::search::bfs;
+//!
+//! // The fewest increments and doublings needed to get from 1 to 10.
+//! let search = bfs(1, |&n| vec![n + 1, n * 2], |&n| n == 10);
+//! assert_eq!(search.distance(&10), Some(4));
+//! assert_eq!(search.path_to(&10), Some(vec![1, 2, 4, 5, 10]));
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::convert::Infallible;
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The outcome of a search: the cost of reaching every settled node, and the
+/// node each was reached from.
+#[derive(Debug, Clone)]
+pub struct Search<N, C> {
+    start: N,
+    distances: HashMap<N, C>,
+    predecessors: HashMap<N, N>,
+    goal: Option<N>,
+}
+
+impl<N, C> Search<N, C>
+where
+    N: Hash + Eq + Clone,
+    C: Copy,
+{
+    /// The goal node, if the search reached one.
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    /// The cost of the cheapest path to `node`, if it was reached.
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    /// The cost of the cheapest path to every node that was reached.
+    pub fn distances(&self) -> &HashMap<N, C> {
+        &self.distances
+    }
+
+    /// The previous node on the cheapest path to every reached node except
+    /// the start.
+    pub fn predecessors(&self) -> &HashMap<N, N> {
+        &self.predecessors
+    }
+
+    /// The cheapest path from the start to `node`, including both ends.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        while let Some(previous) = self.predecessors.get(path.last().unwrap()) {
+            path.push(previous.clone());
+        }
+
+        debug_assert!(path.last() == Some(&self.start));
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search, where every step costs one.
+pub fn bfs<N, S, I, G>(start: N, mut successors: S, is_goal: G) -> Search<N, u64>
+where
+    N: Hash + Eq + Clone,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    G: FnMut(&N) -> bool,
+{
+    let search = try_bfs(start, |node| Ok::<_, Infallible>(successors(node)), is_goal);
+    match search {
+        Ok(search) => search,
+        Err(never) => match never {},
+    }
+}
+
+/// Breadth-first search where listing a node's successors can fail, such as
+/// when it runs a program. The search stops at the first error.
+///
+/// ```
+/// use aoc2019::common::search::try_bfs;
+/// use anyhow::anyhow;
+///
+/// let successors = |&n: &u32| match n {
+///     n if n < 5 => Ok(vec![n + 1]),
+///     n => Err(anyhow!("No successors for {}", n)),
+/// };
+///
+/// assert_eq!(try_bfs(0, successors, |&n| n == 3)?.distance(&3), Some(3));
+/// assert!(try_bfs(0, successors, |&n| n == 9).is_err());
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn try_bfs<N, S, I, G, E>(
+    start: N,
+    mut successors: S,
+    mut is_goal: G,
+) -> Result<Search<N, u64>, E>
+where
+    N: Hash + Eq + Clone,
+    S: FnMut(&N) -> Result<I, E>,
+    I: IntoIterator<Item = N>,
+    G: FnMut(&N) -> bool,
+{
+    let mut distances = HashMap::new();
+    let mut predecessors = HashMap::new();
+    let mut queue = VecDeque::new();
+    let mut goal = None;
+
+    distances.insert(start.clone(), 0);
+    queue.push_back(start.clone());
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            goal = Some(node);
+            break;
+        }
+
+        let distance = distances[&node];
+        for next in successors(&node)? {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                predecessors.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    Ok(Search {
+        start,
+        distances,
+        predecessors,
+        goal,
+    })
+}
+
+/// Dijkstra's algorithm, for successors given with non-negative step costs.
+pub fn dijkstra<N, C, S, I, G>(start: N, successors: S, is_goal: G) -> Search<N, C>
+where
+    N: Hash + Eq + Clone,
+    C: Copy + Ord + Add<Output = C> + Default,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    G: FnMut(&N) -> bool,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// A* search. The heuristic estimates the remaining cost to a goal. Nodes are
+/// settled once and never reopened, so for the result to be a shortest path
+/// the heuristic must be consistent: it never drops by more than the cost of
+/// a step, and is zero at every goal. Consistent heuristics never
+/// overestimate, but not every heuristic that never overestimates is
+/// consistent.
+pub fn astar<N, C, S, I, H, G>(
+    start: N,
+    mut successors: S,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> Search<N, C>
+where
+    N: Hash + Eq + Clone,
+    C: Copy + Ord + Add<Output = C> + Default,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+    G: FnMut(&N) -> bool,
+{
+    let mut distances = HashMap::new();
+    let mut predecessors = HashMap::new();
+    let mut settled = HashSet::new();
+    let mut goal = None;
+
+    // The heap refers to nodes by index, so nodes need not be ordered.
+    let mut nodes = vec![start.clone()];
+    let mut heap = BinaryHeap::new();
+
+    distances.insert(start.clone(), C::default());
+    heap.push(Reverse((heuristic(&start), C::default(), 0)));
+
+    while let Some(Reverse((_, distance, idx))) = heap.pop() {
+        let node = nodes[idx].clone();
+        if distance > distances[&node] || !settled.insert(node.clone()) {
+            continue;
+        }
+
+        if is_goal(&node) {
+            goal = Some(node);
+            break;
+        }
+
+        for (next, cost) in successors(&node) {
+            let next_distance = distance + cost;
+            if settled.contains(&next)
+                || distances
+                    .get(&next)
+                    .is_some_and(|&best| best <= next_distance)
+            {
+                continue;
+            }
+
+            distances.insert(next.clone(), next_distance);
+            predecessors.insert(next.clone(), node.clone());
+            heap.push(Reverse((
+                next_distance + heuristic(&next),
+                next_distance,
+                nodes.len(),
+            )));
+            nodes.push(next);
+        }
+    }
+
+    // Costs of nodes still on the frontier are only upper bounds.
+    distances.retain(|node, _| settled.contains(node));
+    predecessors.retain(|node, _| settled.contains(node));
+
+    Search {
+        start,
+        distances,
+        predecessors,
+        goal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::grid::{Grid, NEIGHBORS4};
+    use crate::common::Point;
+
+    const MAZE: &str = "\
+S...#
+.##.#
+.#..G
+.#.##
+...#.";
+
+    fn open(maze: &Grid<char>) -> impl FnMut(&Point) -> Vec<Point> + '_ {
+        move |&point| {
+            NEIGHBORS4
+                .iter()
+                .map(|&offset| point + offset)
+                .filter(|next| maze.get(next).is_some_and(|&c| c != '#'))
+                .collect()
+        }
+    }
+
+    fn find(maze: &Grid<char>, tile: char) -> Point {
+        maze.iter().find(|(_, &c)| c == tile).unwrap().0
+    }
+
+    #[test]
+    fn test_bfs_grid() {
+        let maze = Grid::parse(MAZE, Some);
+        let (start, goal) = (find(&maze, 'S'), find(&maze, 'G'));
+
+        let search = bfs(start, open(&maze), |&p| p == goal);
+        assert_eq!(search.goal(), Some(&goal));
+        assert_eq!(search.distance(&goal), Some(6));
+
+        let path = search.path_to(&goal).unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path.first(), Some(&start));
+        assert!(path
+            .windows(2)
+            .all(|step| open(&maze)(&step[0]).contains(&step[1])));
+    }
+
+    #[test]
+    fn test_bfs_exhaustive() {
+        let maze = Grid::parse(MAZE, Some);
+        let search = bfs(find(&maze, 'S'), open(&maze), |_| false);
+
+        // The bottom-right corner is walled off.
+        assert_eq!(search.goal(), None);
+        assert_eq!(search.distances().len(), 15);
+        assert_eq!(search.distance(&Point { x: 4, y: 4 }), None);
+        assert_eq!(search.distances().values().max(), Some(&7));
+        assert_eq!(search.predecessors().len(), 14);
+    }
+
+    #[test]
+    fn test_weighted_searches_agree() {
+        // Moving right is cheap and moving down is expensive, so the cheapest
+        // route to (3, 3) is not the first one found by hop count.
+        let successors = |&(x, y): &(i64, i64)| {
+            let mut next = vec![];
+            if x < 5 {
+                next.push(((x + 1, y), 1u64));
+            }
+            if y < 5 {
+                next.push(((x, y + 1), 2 + x as u64));
+            }
+            next
+        };
+        let goal = (3, 3);
+
+        let plain = dijkstra((0, 0), successors, |&n| n == goal);
+        let guided = astar(
+            (0, 0),
+            successors,
+            |&(x, y): &(i64, i64)| (goal.0 - x).max(0) as u64 + 2 * (goal.1 - y).max(0) as u64,
+            |&n| n == goal,
+        );
+
+        assert_eq!(plain.distance(&goal), Some(9));
+        assert_eq!(guided.distance(&goal), Some(9));
+        assert_eq!(
+            plain.path_to(&goal).unwrap()[..4],
+            [(0, 0), (0, 1), (0, 2), (0, 3)]
+        );
+        assert!(guided.distances().len() <= plain.distances().len());
+    }
+
+    #[test]
+    fn test_unreachable_goal() {
+        let search = dijkstra(0u32, |&n| vec![((n + 1) % 4, 1u32)], |&n| n == 7);
+        assert_eq!(search.goal(), None);
+        assert_eq!(search.distances().len(), 4);
+        assert_eq!(search.path_to(&7), None);
+    }
+}
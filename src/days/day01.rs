@@ -1,3 +1,4 @@
+use crate::days::{Example, Solution};
 use anyhow::Result;
 
 pub fn part1(input_string: &str) -> Result<String> {
@@ -23,3 +24,31 @@ fn get_fuel_requirement(input_string: &str, mass_to_fuel: fn(u64) -> u64) -> Str
         .sum::<u64>()
         .to_string()
 }
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "The Tyranny of the Rocket Equation"
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        part2(input)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        const EXAMPLES: &[Example] = &[
+            Example::new(1, "12\n14\n1969\n100756", "34241"),
+            Example::new(2, "14\n1969\n100756", "51314"),
+        ];
+        EXAMPLES
+    }
+}
@@ -1,30 +1,69 @@
+use crate::days::{Example, Solution};
 use crate::intcode::{Computer, IndexedParameter};
 use anyhow::{anyhow, Result};
 use itertools::iproduct;
 
-pub fn part1(source: &str) -> Result<String> {
+// Runs the program, optionally patching in a noun and verb first, and returns
+// the value left at address 0.
+fn run_program(source: &str, noun_verb: Option<(i64, i64)>) -> Result<i64> {
     let mut computer = Computer::new_from_str(source)?;
-    computer.set_value(IndexedParameter::Positional(1), 12);
-    computer.set_value(IndexedParameter::Positional(2), 2);
+    if let Some((noun, verb)) = noun_verb {
+        computer.set_value(IndexedParameter::Positional(1), noun)?;
+        computer.set_value(IndexedParameter::Positional(2), verb)?;
+    }
 
     computer.run(vec![])?;
-    Ok(computer.get_memory_value(0).to_string())
+    Ok(computer.get_memory_value(0))
+}
+
+pub fn part1(source: &str) -> Result<String> {
+    Ok(run_program(source, Some((12, 2)))?.to_string())
 }
 
 pub fn part2(source: &str) -> Result<String> {
     let target = 19690720;
 
     for (noun, verb) in iproduct!(0..100, 0..100) {
-        let mut computer = Computer::new_from_str(source)?;
-        computer.set_value(IndexedParameter::Positional(1), noun);
-        computer.set_value(IndexedParameter::Positional(2), verb);
-
-        computer.run(vec![])?;
-
-        if computer.get_memory_value(0) == target {
+        if run_program(source, Some((noun, verb)))? == target {
             return Ok((100 * noun + verb).to_string());
         }
     }
 
     Err(anyhow!("Unable to find valid noun/verb combination"))
 }
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "1202 Program Alarm"
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        part2(input)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        const EXAMPLES: &[Example] = &[
+            Example::new(1, "1,9,10,3,2,3,11,0,99,30,40,50", "3500"),
+            Example::new(1, "1,0,0,0,99", "2"),
+            Example::new(1, "2,3,0,3,99", "2"),
+            Example::new(1, "2,4,4,5,99,0", "2"),
+            Example::new(1, "1,1,1,4,99,5,6,0,99", "30"),
+        ];
+        EXAMPLES
+    }
+
+    // The published examples run as written, without the 1202 patch.
+    fn run_example(&self, example: &Example) -> Result<String> {
+        Ok(run_program(example.input, None)?.to_string())
+    }
+}
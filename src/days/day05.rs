@@ -1,92 +1,72 @@
+use crate::days::{Example, Solution};
 use crate::intcode::Computer;
 use anyhow::Result;
+use std::convert::TryFrom;
 
-pub fn part1(source: &str) -> Result<String> {
-    let output = Computer::new_from_str(source)?.run(vec![1])?;
+fn run_with_input(source: &str, input: i64) -> Result<String> {
+    let output = Computer::new_from_str(source)?.run(vec![input])?;
     Ok(format!("{:?}", output))
 }
 
-pub fn part2(source: &str) -> Result<String> {
-    let output = Computer::new_from_str(source)?.run(vec![5])?;
-    Ok(format!("{:?}", output))
+pub fn part1(source: &str) -> Result<String> {
+    run_with_input(source, 1)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_position_equals() -> Result<()> {
-        let source = vec![3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
-
-        assert_eq!(Computer::new(&source).run(vec![8])?, vec![1]);
-        assert_eq!(Computer::new(&source).run(vec![2])?, vec![0]);
-
-        Ok(())
-    }
-
-    #[test]
-    fn test_position_less_than() -> Result<()> {
-        let source = vec![3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8];
-
-        assert_eq!(Computer::new(&source).run(vec![7])?, vec![1]);
-        assert_eq!(Computer::new(&source).run(vec![9])?, vec![0]);
-
-        Ok(())
-    }
+pub fn part2(source: &str) -> Result<String> {
+    run_with_input(source, 5)
+}
 
-    #[test]
-    fn test_immediate_equals() -> Result<()> {
-        let source = vec![3, 3, 1108, -1, 8, 3, 4, 3, 99];
+const COMPARE_TO_EIGHT: &str = "\
+3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,\
+0,1002,21,125,20,4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,\
+20,1105,1,46,98,99";
 
-        assert_eq!(Computer::new(&source).run(vec![8])?, vec![1]);
-        assert_eq!(Computer::new(&source).run(vec![2])?, vec![0]);
+pub struct Day05;
 
-        Ok(())
+impl Solution for Day05 {
+    fn day(&self) -> u32 {
+        5
     }
 
-    #[test]
-    fn test_immediate_less_than() -> Result<()> {
-        let source = vec![3, 3, 1107, -1, 8, 3, 4, 3, 99];
-
-        assert_eq!(Computer::new(&source).run(vec![7])?, vec![1]);
-        assert_eq!(Computer::new(&source).run(vec![9])?, vec![0]);
-
-        Ok(())
+    fn title(&self) -> &'static str {
+        "Sunny with a Chance of Asteroids"
     }
 
-    #[test]
-    fn test_position_jump() -> Result<()> {
-        let source = vec![3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9];
-
-        assert_eq!(Computer::new(&source).run(vec![0])?, vec![0]);
-        assert_eq!(Computer::new(&source).run(vec![42])?, vec![1]);
-
-        Ok(())
+    fn part1(&self, input: &str) -> Result<String> {
+        part1(input)
     }
 
-    #[test]
-    fn test_immediate_jump() -> Result<()> {
-        let source = vec![3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1];
-
-        assert_eq!(Computer::new(&source).run(vec![0])?, vec![0]);
-        assert_eq!(Computer::new(&source).run(vec![42])?, vec![1]);
-
-        Ok(())
+    fn part2(&self, input: &str) -> Result<String> {
+        part2(input)
     }
 
-    #[test]
-    fn test_large_example() -> Result<()> {
-        let source = vec![
-            3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0,
-            0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4,
-            20, 1105, 1, 46, 98, 99,
+    // Each example's setting is the value fed to the program.
+    fn examples(&self) -> &'static [Example] {
+        const EXAMPLES: &[Example] = &[
+            Example::new(1, "3,0,4,0,99", "[42]").with_setting(42),
+            Example::new(2, "3,9,8,9,10,9,4,9,99,-1,8", "[1]").with_setting(8),
+            Example::new(2, "3,9,8,9,10,9,4,9,99,-1,8", "[0]").with_setting(2),
+            Example::new(2, "3,9,7,9,10,9,4,9,99,-1,8", "[1]").with_setting(7),
+            Example::new(2, "3,9,7,9,10,9,4,9,99,-1,8", "[0]").with_setting(9),
+            Example::new(2, "3,3,1108,-1,8,3,4,3,99", "[1]").with_setting(8),
+            Example::new(2, "3,3,1108,-1,8,3,4,3,99", "[0]").with_setting(2),
+            Example::new(2, "3,3,1107,-1,8,3,4,3,99", "[1]").with_setting(7),
+            Example::new(2, "3,3,1107,-1,8,3,4,3,99", "[0]").with_setting(9),
+            Example::new(2, "3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9", "[0]").with_setting(0),
+            Example::new(2, "3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9", "[1]").with_setting(42),
+            Example::new(2, "3,3,1105,-1,9,1101,0,0,12,4,12,99,1", "[0]").with_setting(0),
+            Example::new(2, "3,3,1105,-1,9,1101,0,0,12,4,12,99,1", "[1]").with_setting(42),
+            Example::new(2, COMPARE_TO_EIGHT, "[999]").with_setting(7),
+            Example::new(2, COMPARE_TO_EIGHT, "[1000]").with_setting(8),
+            Example::new(2, COMPARE_TO_EIGHT, "[1001]").with_setting(9),
         ];
+        EXAMPLES
+    }
 
-        assert_eq!(Computer::new(&source).run(vec![7])?, vec![999]);
-        assert_eq!(Computer::new(&source).run(vec![8])?, vec![1000]);
-        assert_eq!(Computer::new(&source).run(vec![9])?, vec![1001]);
-
-        Ok(())
+    fn run_example(&self, example: &Example) -> Result<String> {
+        match example.setting {
+            Some(input) => run_with_input(example.input, i64::try_from(input)?),
+            None => self.run(example.part, example.input),
+        }
     }
 }
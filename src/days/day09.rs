@@ -1,3 +1,4 @@
+use crate::days::{Example, Solution};
 use crate::intcode::Computer;
 use anyhow::Result;
 
@@ -11,33 +12,35 @@ pub fn part2(source: &str) -> Result<String> {
     Ok(format!("{:?}", output))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day09;
 
-    #[test]
-    fn test_quine() -> Result<()> {
-        let source = vec![
-            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
-        ];
-
-        assert_eq!(Computer::new(&source).run(vec![])?, source);
-        Ok(())
+impl Solution for Day09 {
+    fn day(&self) -> u32 {
+        9
     }
 
-    #[test]
-    fn test_sixteen_digit() -> Result<()> {
-        let source = vec![1102, 34915192, 34915192, 7, 4, 7, 99, 0];
+    fn title(&self) -> &'static str {
+        "Sensor Boost"
+    }
 
-        assert_eq!(Computer::new(&source).run(vec![])?, vec![1219070632396864]);
-        Ok(())
+    fn part1(&self, input: &str) -> Result<String> {
+        part1(input)
     }
 
-    #[test]
-    fn test_large_number() -> Result<()> {
-        let source = vec![104, 1125899906842624, 99];
+    fn part2(&self, input: &str) -> Result<String> {
+        part2(input)
+    }
 
-        assert_eq!(Computer::new(&source).run(vec![])?, vec![1125899906842624]);
-        Ok(())
+    fn examples(&self) -> &'static [Example] {
+        const EXAMPLES: &[Example] = &[
+            Example::new(
+                1,
+                "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99",
+                "[109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99]",
+            ),
+            Example::new(1, "1102,34915192,34915192,7,4,7,99,0", "[1219070632396864]"),
+            Example::new(1, "104,1125899906842624,99", "[1125899906842624]"),
+        ];
+        EXAMPLES
     }
 }
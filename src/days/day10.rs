@@ -0,0 +1,197 @@
+use crate::common::math::{compare_clockwise, gcd};
+use crate::common::Point;
+use crate::days::{Example, Solution};
+use anyhow::{anyhow, Result};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+fn parse_asteroids(input: &str) -> Result<Vec<Point>> {
+    let mut asteroids = vec![];
+
+    for (y, line) in input.lines().enumerate() {
+        for (x, c) in line.trim().chars().enumerate() {
+            match c {
+                '#' => asteroids.push(Point {
+                    x: x as i64,
+                    y: y as i64,
+                }),
+                '.' => {}
+                _ => return Err(anyhow!("Unexpected character {:?} at {},{}", c, x, y)),
+            }
+        }
+    }
+
+    Ok(asteroids)
+}
+
+fn reduced_direction(from: Point, to: Point) -> Point {
+    let delta = to - from;
+    let divisor = gcd(delta.x, delta.y);
+
+    Point {
+        x: delta.x / divisor,
+        y: delta.y / divisor,
+    }
+}
+
+fn count_visible(station: Point, asteroids: &[Point]) -> usize {
+    asteroids
+        .iter()
+        .filter(|&&asteroid| asteroid != station)
+        .map(|&asteroid| reduced_direction(station, asteroid))
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+fn find_station(asteroids: &[Point]) -> Result<(Point, usize)> {
+    asteroids
+        .iter()
+        .map(|&station| (station, count_visible(station, asteroids)))
+        .max_by_key(|&(_, visible)| visible)
+        .ok_or_else(|| anyhow!("No asteroids in input"))
+}
+
+fn vaporization_order(station: Point, asteroids: &[Point]) -> Vec<Point> {
+    let mut by_direction: HashMap<Point, Vec<Point>> = HashMap::new();
+    for &asteroid in asteroids.iter().filter(|&&asteroid| asteroid != station) {
+        by_direction
+            .entry(reduced_direction(station, asteroid))
+            .or_default()
+            .push(asteroid);
+    }
+
+    let mut directions: Vec<Point> = by_direction.keys().copied().collect();
+    directions.sort_by(compare_clockwise);
+
+    // Each line of sight is a queue ordered from nearest to farthest.
+    let mut queues: Vec<VecDeque<Point>> = directions
+        .iter()
+        .map(|direction| {
+            let mut line = by_direction.remove(direction).unwrap();
+            line.sort_by_key(|asteroid| asteroid.manhattan_distance(&station));
+            line.into_iter().collect()
+        })
+        .collect();
+
+    let mut order = vec![];
+    while order.len() < asteroids.len() - 1 {
+        for queue in queues.iter_mut() {
+            if let Some(asteroid) = queue.pop_front() {
+                order.push(asteroid);
+            }
+        }
+    }
+
+    order
+}
+
+pub fn part1(input: &str) -> Result<String> {
+    let asteroids = parse_asteroids(input)?;
+    let (_, visible) = find_station(&asteroids)?;
+
+    Ok(visible.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    let asteroids = parse_asteroids(input)?;
+    let (station, _) = find_station(&asteroids)?;
+
+    let order = vaporization_order(station, &asteroids);
+    let target = order
+        .get(199)
+        .ok_or_else(|| anyhow!("Only {} asteroids were vaporized", order.len()))?;
+
+    Ok((target.x * 100 + target.y).to_string())
+}
+
+const SMALL_EXAMPLE: &str = ".#..#\n.....\n#####\n....#\n...##";
+
+const LARGE_EXAMPLE: &str = "\
+.#..##.###...#######
+##.############..##.
+.#.######.########.#
+.###.#######.####.#.
+#####.##.#.##.###.##
+..#####..#.#########
+####################
+#.####....###.#.#.##
+##.#################
+#####.##.###..####..
+..######..##.#######
+####.##.####...##..#
+.#####..#.######.###
+##...#.##########...
+#.##########.#######
+.####.#.###.###.#.##
+....##.##.###..#####
+.#.#.###########.###
+#.#.#.#####.####.###
+###.##.####.##.#..##";
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn title(&self) -> &'static str {
+        "Monitoring Station"
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        part2(input)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        const EXAMPLES: &[Example] = &[
+            Example::new(1, SMALL_EXAMPLE, "8"),
+            Example::new(1, LARGE_EXAMPLE, "210"),
+            Example::new(2, LARGE_EXAMPLE, "802"),
+        ];
+        EXAMPLES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_station() -> Result<()> {
+        let asteroids = parse_asteroids(SMALL_EXAMPLE)?;
+
+        assert_eq!(find_station(&asteroids)?, (Point { x: 3, y: 4 }, 8));
+        Ok(())
+    }
+
+    #[test]
+    fn test_large_station() -> Result<()> {
+        let asteroids = parse_asteroids(LARGE_EXAMPLE)?;
+
+        assert_eq!(find_station(&asteroids)?, (Point { x: 11, y: 13 }, 210));
+        Ok(())
+    }
+
+    #[test]
+    fn test_vaporization_order() -> Result<()> {
+        let asteroids = parse_asteroids(LARGE_EXAMPLE)?;
+        let order = vaporization_order(Point { x: 11, y: 13 }, &asteroids);
+
+        assert_eq!(order[0], Point { x: 11, y: 12 });
+        assert_eq!(order[1], Point { x: 12, y: 1 });
+        assert_eq!(order[2], Point { x: 12, y: 2 });
+        assert_eq!(order[9], Point { x: 12, y: 8 });
+        assert_eq!(order[19], Point { x: 16, y: 0 });
+        assert_eq!(order[49], Point { x: 16, y: 9 });
+        assert_eq!(order[99], Point { x: 10, y: 16 });
+        assert_eq!(order[198], Point { x: 9, y: 6 });
+        assert_eq!(order[199], Point { x: 8, y: 2 });
+        assert_eq!(order[200], Point { x: 10, y: 9 });
+        assert_eq!(order[298], Point { x: 11, y: 1 });
+        Ok(())
+    }
+}
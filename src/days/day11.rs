@@ -1,9 +1,11 @@
-use crate::common::{Direction, Point};
+use crate::common::{Direction, Grid, Point, YAxis};
+use crate::days::{Example, Solution};
 use crate::intcode::{Computer, StoppedResult};
 use anyhow::Result;
-use itertools::join;
 use std::collections::HashSet;
-use std::iter::FromIterator;
+
+// The robot starts facing up; the hull is treated as a Cartesian plane.
+const HULL_AXIS: YAxis = YAxis::Up;
 
 #[derive(Debug, Copy, Clone)]
 enum Color {
@@ -77,7 +79,7 @@ impl Iterator for ShipHullExplorer {
         let output = Some((self.position, color));
 
         self.status = status;
-        self.position = self.position + self.direction.get_offset();
+        self.position += self.direction.offset(HULL_AXIS);
 
         output
     }
@@ -99,7 +101,7 @@ pub fn part2(source: &str) -> Result<String> {
     white.insert(Point { x: 0, y: 0 });
 
     let explorer = ShipHullExplorer::new(source, white)?;
-    let mut white_points: HashSet<Point> = HashSet::new();
+    let mut white_points = Grid::sparse(HULL_AXIS);
 
     for (point, color) in explorer {
         match color {
@@ -107,35 +109,45 @@ pub fn part2(source: &str) -> Result<String> {
                 white_points.remove(&point);
             }
             Color::White => {
-                white_points.insert(point);
+                white_points.insert(point, ());
             }
         }
     }
 
-    let points = Vec::from_iter(white_points.clone());
-    let points_slice = &points[..];
-
-    let min_x = points_slice.into_iter().min_by_key(|p| p.x).unwrap().x;
-    let min_y = points_slice.into_iter().min_by_key(|p| p.y).unwrap().y;
-    let max_x = points_slice.into_iter().max_by_key(|p| p.x).unwrap().x;
-    let max_y = points_slice.into_iter().max_by_key(|p| p.y).unwrap().y;
-
-    let output = join(
-        (min_y..max_y + 1).rev().map(|y| {
-            join(
-                (min_x..max_x + 1).map(|x| {
-                    let point = Point { x, y };
-                    if white_points.contains(&point) {
-                        '#'
-                    } else {
-                        ' '
-                    }
-                }),
-                "",
-            )
-        }),
-        "\n",
-    );
-
-    Ok(output)
+    Ok(white_points.render(|tile| match tile {
+        Some(()) => '#',
+        None => ' ',
+    }))
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn day(&self) -> u32 {
+        11
+    }
+
+    fn title(&self) -> &'static str {
+        "Space Police"
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        part2(input)
+    }
+
+    // The puzzle publishes the robot's outputs rather than a program, so this
+    // one reads a panel color before giving each published pair of outputs.
+    fn examples(&self) -> &'static [Example] {
+        const EXAMPLES: &[Example] = &[Example::new(
+            1,
+            "3,100,104,1,104,0,3,100,104,0,104,0,3,100,104,1,104,0,3,100,104,1,104,0,\
+             3,100,104,0,104,1,3,100,104,1,104,0,3,100,104,1,104,0,99",
+            "6",
+        )];
+        EXAMPLES
+    }
 }
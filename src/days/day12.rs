@@ -0,0 +1,169 @@
+use crate::common::math::lcm;
+use crate::common::Point3;
+use crate::days::{no_steps, Example, Solution};
+use anyhow::{anyhow, Result};
+use regex::Regex;
+
+const STEPS: usize = 1000;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Moon {
+    position: Point3,
+    velocity: Point3,
+}
+
+impl Moon {
+    fn energy(&self) -> i64 {
+        self.position.manhattan_length() * self.velocity.manhattan_length()
+    }
+}
+
+fn parse_moons(input: &str) -> Result<Vec<Moon>> {
+    let re = Regex::new(r"^<x=(-?\d+), y=(-?\d+), z=(-?\d+)>$")?;
+
+    input
+        .lines()
+        .map(|line| {
+            let captures = re
+                .captures(line.trim())
+                .ok_or_else(|| anyhow!("Invalid moon position {:?}", line))?;
+
+            Ok(Moon {
+                position: Point3 {
+                    x: captures[1].parse()?,
+                    y: captures[2].parse()?,
+                    z: captures[3].parse()?,
+                },
+                velocity: Point3::default(),
+            })
+        })
+        .collect()
+}
+
+fn gravity(a: i64, b: i64) -> i64 {
+    (b - a).signum()
+}
+
+fn step(moons: &mut [Moon]) {
+    for i in 0..moons.len() {
+        let position = moons[i].position;
+        let pull = moons
+            .iter()
+            .map(|other| Point3 {
+                x: gravity(position.x, other.position.x),
+                y: gravity(position.y, other.position.y),
+                z: gravity(position.z, other.position.z),
+            })
+            .fold(Point3::default(), |acc, p| acc + p);
+
+        moons[i].velocity += pull;
+    }
+
+    for moon in moons.iter_mut() {
+        moon.position += moon.velocity;
+    }
+}
+
+// The axes evolve independently, so each one repeats with its own period and
+// the whole system repeats at their least common multiple. The simulation is
+// reversible, so the first repeated state is always the initial one.
+fn axis_period(moons: &[Moon], axis: fn(&Point3) -> i64) -> i64 {
+    let initial: Vec<(i64, i64)> = moons
+        .iter()
+        .map(|moon| (axis(&moon.position), axis(&moon.velocity)))
+        .collect();
+
+    let mut moons = moons.to_vec();
+    let mut steps = 0;
+
+    loop {
+        step(&mut moons);
+        steps += 1;
+
+        if moons
+            .iter()
+            .map(|moon| (axis(&moon.position), axis(&moon.velocity)))
+            .eq(initial.iter().copied())
+        {
+            return steps;
+        }
+    }
+}
+
+pub fn total_energy_after(input: &str, steps: usize) -> Result<String> {
+    let mut moons = parse_moons(input)?;
+    for _ in 0..steps {
+        step(&mut moons);
+    }
+
+    Ok(moons.iter().map(Moon::energy).sum::<i64>().to_string())
+}
+
+pub fn part1(input: &str) -> Result<String> {
+    total_energy_after(input, STEPS)
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    let moons = parse_moons(input)?;
+
+    let period = [
+        axis_period(&moons, |p| p.x),
+        axis_period(&moons, |p| p.y),
+        axis_period(&moons, |p| p.z),
+    ]
+    .iter()
+    .fold(1, |acc, &period| lcm(acc, period));
+
+    Ok(period.to_string())
+}
+
+const FIRST_EXAMPLE: &str = "\
+<x=-1, y=0, z=2>
+<x=2, y=-10, z=-7>
+<x=4, y=-8, z=8>
+<x=3, y=5, z=-1>";
+
+const SECOND_EXAMPLE: &str = "\
+<x=-8, y=-10, z=0>
+<x=5, y=5, z=10>
+<x=2, y=-7, z=3>
+<x=9, y=-8, z=-3>";
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    fn day(&self) -> u32 {
+        12
+    }
+
+    fn title(&self) -> &'static str {
+        "The N-Body Problem"
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        part2(input)
+    }
+
+    // The energy examples run for fewer steps than the puzzle; the setting is
+    // the step count.
+    fn examples(&self) -> &'static [Example] {
+        const EXAMPLES: &[Example] = &[
+            Example::new(1, FIRST_EXAMPLE, "179").with_setting(10),
+            Example::new(1, SECOND_EXAMPLE, "1940").with_setting(100),
+            Example::new(2, FIRST_EXAMPLE, "2772"),
+            Example::new(2, SECOND_EXAMPLE, "4686774924"),
+        ];
+        EXAMPLES
+    }
+
+    fn run_with_steps(&self, part: u32, input: &str, steps: usize) -> Result<String> {
+        match part {
+            1 => total_energy_after(input, steps),
+            _ => Err(no_steps(self.day(), part)),
+        }
+    }
+}
@@ -1,33 +1,60 @@
-use crate::common::care_package::{Direction, Game};
+use crate::common::care_package::{autopilot, Game};
+use crate::days::{Example, Solution};
 use anyhow::Result;
 
 pub fn part1(source: &str) -> Result<String> {
     let mut game = Game::new(source, false)?;
-    let screen = game.do_move(Direction::Neutral)?;
+    let screen = game.do_move(None)?;
 
     Ok(screen.remaining_blocks.to_string())
 }
 
 pub fn part2(source: &str) -> Result<String> {
     let mut game = Game::new(source, true)?;
-    let mut direction = Direction::Neutral;
+    let mut tilt = None;
 
     loop {
-        let screen = game.do_move(direction)?;
-
-        // print!("{}[2J", 27 as char);
-        // println!("{}", screen);
-
+        let screen = game.do_move(tilt)?;
         if screen.remaining_blocks == 0 {
             return Ok(screen.score.to_string());
         }
 
-        direction = if screen.paddle.x < screen.ball.x {
-            Direction::Right
-        } else if screen.paddle.x > screen.ball.x {
-            Direction::Left
-        } else {
-            Direction::Neutral
-        };
+        tilt = autopilot(screen);
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    fn day(&self) -> u32 {
+        13
+    }
+
+    fn title(&self) -> &'static str {
+        "Care Package"
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        part2(input)
+    }
+
+    // A hand-made cabinet that draws two blocks, a paddle and a ball, and
+    // halts unless given free play. Its first move clears both blocks for a
+    // score of 50.
+    fn examples(&self) -> &'static [Example] {
+        const CABINET: &str = "\
+1,0,0,0,104,0,104,0,104,2,104,1,104,0,104,2,104,0,104,2,104,3,104,1,104,1,104,4,\
+1008,0,2,100,1005,100,55,3,101,104,0,104,0,104,0,104,1,104,0,104,0,104,-1,104,0,\
+104,50,99";
+
+        const EXAMPLES: &[Example] = &[
+            Example::new(1, CABINET, "2"),
+            Example::new(2, CABINET, "50"),
+        ];
+        EXAMPLES
     }
 }
@@ -0,0 +1,247 @@
+use crate::days::{Example, Solution};
+use anyhow::{anyhow, Context, Result};
+use petgraph::algo::toposort;
+use petgraph::graphmap::DiGraphMap;
+use std::collections::HashMap;
+
+const ORE: &str = "ORE";
+const FUEL: &str = "FUEL";
+const ORE_SUPPLY: u64 = 1_000_000_000_000;
+
+// Edges point from a product to each of its ingredients, weighted by the
+// quantity of that ingredient consumed per batch.
+#[derive(Debug, Clone)]
+struct Reactions<'a> {
+    graph: DiGraphMap<&'a str, u64>,
+    batch_sizes: HashMap<&'a str, u64>,
+}
+
+type Quantity<'a> = (&'a str, u64);
+
+fn parse_quantity(term: &str) -> Result<Quantity<'_>> {
+    let mut parts = term.split_whitespace();
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(quantity), Some(chemical), None) => match quantity.parse()? {
+            0 => Err(anyhow!("Quantity of {} must be positive", chemical)),
+            quantity => Ok((chemical, quantity)),
+        },
+        _ => Err(anyhow!("Expected `<quantity> <chemical>`, got {:?}", term)),
+    }
+}
+
+fn parse_reaction(line: &str) -> Result<(Quantity<'_>, Vec<Quantity<'_>>)> {
+    let mut sides = line.split("=>");
+    match (sides.next(), sides.next(), sides.next()) {
+        (Some(inputs), Some(output), None) => Ok((
+            parse_quantity(output)?,
+            inputs
+                .split(',')
+                .map(parse_quantity)
+                .collect::<Result<_>>()?,
+        )),
+        _ => Err(anyhow!("Expected exactly one `=>`")),
+    }
+}
+
+impl<'a> Reactions<'a> {
+    fn new(input: &'a str) -> Result<Self> {
+        let mut graph = DiGraphMap::new();
+        let mut batch_sizes = HashMap::new();
+
+        for (idx, line) in input.lines().enumerate() {
+            let ((product, batch_size), ingredients) = parse_reaction(line)
+                .with_context(|| format!("Invalid reaction on line {}: {:?}", idx + 1, line))?;
+
+            if batch_sizes.insert(product, batch_size).is_some() {
+                return Err(anyhow!(
+                    "Invalid reaction on line {}: {} is produced more than once",
+                    idx + 1,
+                    product
+                ));
+            }
+
+            graph.add_node(product);
+            for (ingredient, quantity) in ingredients {
+                graph.add_edge(product, ingredient, quantity);
+            }
+        }
+
+        if !batch_sizes.contains_key(FUEL) {
+            return Err(anyhow!("No reaction produces {}", FUEL));
+        }
+
+        Ok(Self { graph, batch_sizes })
+    }
+
+    // Returns the ORE consumed and the chemicals left over after producing the
+    // requested amount of FUEL. Processing chemicals in topological order
+    // guarantees every consumer of a chemical has already placed its demand.
+    fn produce(&self, fuel: u64) -> Result<(u64, HashMap<&'a str, u64>)> {
+        let order = toposort(&self.graph, None)
+            .map_err(|cycle| anyhow!("Reactions contain a cycle at {}", cycle.node_id()))?;
+
+        let mut required: HashMap<&str, u64> = HashMap::new();
+        let mut leftovers: HashMap<&str, u64> = HashMap::new();
+        required.insert(FUEL, fuel);
+
+        for chemical in order {
+            if chemical == ORE {
+                continue;
+            }
+
+            let needed = required.get(chemical).copied().unwrap_or(0);
+            let batch_size = *self
+                .batch_sizes
+                .get(chemical)
+                .ok_or_else(|| anyhow!("No reaction produces {}", chemical))?;
+
+            let batches = needed.div_ceil(batch_size);
+            let surplus = batches * batch_size - needed;
+            if surplus > 0 {
+                leftovers.insert(chemical, surplus);
+            }
+
+            for (_, ingredient, &quantity) in self.graph.edges(chemical) {
+                *required.entry(ingredient).or_insert(0) += batches * quantity;
+            }
+        }
+
+        Ok((required.get(ORE).copied().unwrap_or(0), leftovers))
+    }
+
+    fn ore_required(&self, fuel: u64) -> Result<u64> {
+        Ok(self.produce(fuel)?.0)
+    }
+}
+
+pub fn part1(input: &str) -> Result<String> {
+    let reactions = Reactions::new(input)?;
+    Ok(reactions.ore_required(1)?.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    let reactions = Reactions::new(input)?;
+
+    // Leftovers make each additional FUEL cheaper on average, so the answer is
+    // at least the naive quotient. Grow an upper bound, then binary search.
+    let ore_per_fuel = reactions.ore_required(1)?;
+    if ore_per_fuel == 0 {
+        return Err(anyhow!("{} can be made without {}", FUEL, ORE));
+    }
+
+    let mut low = ORE_SUPPLY / ore_per_fuel;
+    let mut high = low.max(1) * 2;
+    while reactions.ore_required(high)? <= ORE_SUPPLY {
+        low = high;
+        high *= 2;
+    }
+
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if reactions.ore_required(mid)? <= ORE_SUPPLY {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    Ok(low.to_string())
+}
+
+const SMALL_EXAMPLE: &str = "\
+10 ORE => 10 A
+1 ORE => 1 B
+7 A, 1 B => 1 C
+7 A, 1 C => 1 D
+7 A, 1 D => 1 E
+7 A, 1 E => 1 FUEL";
+
+const MEDIUM_EXAMPLE: &str = "\
+9 ORE => 2 A
+8 ORE => 3 B
+7 ORE => 5 C
+3 A, 4 B => 1 AB
+5 B, 7 C => 1 BC
+4 C, 1 A => 1 CA
+2 AB, 3 BC, 4 CA => 1 FUEL";
+
+const LARGE_EXAMPLE: &str = "\
+157 ORE => 5 NZVS
+165 ORE => 6 DCFZ
+44 XJWVT, 5 KHKGT, 1 QDVJ, 29 NZVS, 9 GPVTF, 48 HKGWZ => 1 FUEL
+12 HKGWZ, 1 GPVTF, 8 PSHF => 9 QDVJ
+179 ORE => 7 PSHF
+177 ORE => 5 HKGWZ
+7 DCFZ, 7 PSHF => 2 XJWVT
+165 ORE => 2 GPVTF
+3 DCFZ, 7 NZVS, 5 HKGWZ, 10 PSHF => 8 KHKGT";
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    fn day(&self) -> u32 {
+        14
+    }
+
+    fn title(&self) -> &'static str {
+        "Space Stoichiometry"
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        part2(input)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        const EXAMPLES: &[Example] = &[
+            Example::new(1, SMALL_EXAMPLE, "31"),
+            Example::new(1, MEDIUM_EXAMPLE, "165"),
+            Example::new(1, LARGE_EXAMPLE, "13312"),
+            Example::new(2, LARGE_EXAMPLE, "82892753"),
+        ];
+        EXAMPLES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leftovers() -> Result<()> {
+        let reactions = Reactions::new(SMALL_EXAMPLE)?;
+        let (ore, leftovers) = reactions.produce(1)?;
+
+        assert_eq!(ore, 31);
+        assert_eq!(leftovers.get("A"), Some(&2));
+        assert_eq!(leftovers.get("B"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_fuel() {
+        for input in &["10 ORE => 10 A\n7 A => 1 B", ""] {
+            assert!(part1(input).is_err());
+            assert!(part2(input).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_error_reports_line() {
+        let error = Reactions::new("10 ORE => 10 A\n7 A 1 B => 1 C").unwrap_err();
+
+        assert!(format!("{}", error).contains("line 2"));
+    }
+
+    #[test]
+    fn test_zero_quantity() {
+        let error = Reactions::new("1 ORE => 1 FUEL\n1 ORE => 0 A").unwrap_err();
+        assert!(format!("{}", error).contains("line 2"));
+
+        assert!(part1("0 ORE => 1 FUEL").is_err());
+        assert!(part1("1 ORE => 0 A\n1 A => 1 FUEL").is_err());
+    }
+}
@@ -1,146 +1,184 @@
-use crate::common::Point;
+use crate::common::search::{bfs, try_bfs};
+use crate::common::{Direction, Grid, Point, YAxis};
+use crate::days::{Example, Solution};
 use crate::intcode::Computer;
 use anyhow::{anyhow, Result};
-use std::collections::{HashMap, VecDeque};
+use std::collections::HashMap;
+use std::convert::TryFrom;
 
+// The repair droid's encoding of a move.
 #[derive(Debug, Copy, Clone)]
-enum Direction {
-    North,
-    South,
-    West,
-    East,
+struct MovementCommand(i64);
+
+impl TryFrom<Direction> for MovementCommand {
+    type Error = anyhow::Error;
+
+    fn try_from(direction: Direction) -> Result<Self> {
+        match direction {
+            Direction::North => Ok(MovementCommand(1)),
+            Direction::South => Ok(MovementCommand(2)),
+            Direction::West => Ok(MovementCommand(3)),
+            Direction::East => Ok(MovementCommand(4)),
+            _ => Err(anyhow!("The droid cannot move {:?}", direction)),
+        }
+    }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 enum Location {
     Wall,
-    Traversable(TraversableLocation),
-}
-
-#[derive(Debug, Copy, Clone)]
-enum TraversableLocation {
     Empty,
     Oxygen,
 }
 
 #[derive(Debug, Clone)]
 struct Maze {
-    queue: VecDeque<(Computer, TraversableLocation, Point, u64)>,
-    visited: HashMap<Point, u64>,
+    map: Grid<Location>,
+    oxygen: Point,
 }
 
 impl Maze {
-    pub fn new(source: &str) -> Result<Maze> {
-        Self::new_from_computer(Computer::new_from_str(source)?)
-    }
-
-    pub fn new_from_computer(computer: Computer) -> Result<Maze> {
+    // Maps the whole area breadth first. Every open tile reached has a droid
+    // parked on it, which is cloned to try each unexplored move.
+    fn explore(source: &str) -> Result<Self> {
         let start = Point { x: 0, y: 0 };
 
-        let mut queue = VecDeque::new();
-        queue.push_back((computer, TraversableLocation::Empty, start, 0));
-
-        Ok(Maze {
-            queue,
-            visited: HashMap::new(),
-        })
-    }
-}
+        let mut map = Grid::sparse(YAxis::Up);
+        map.insert(start, Location::Empty);
+
+        let mut droids = HashMap::new();
+        droids.insert(start, Computer::new_from_str(source)?);
+
+        try_bfs(
+            start,
+            |&point| -> Result<Vec<Point>> {
+                let droid = droids
+                    .remove(&point)
+                    .ok_or_else(|| anyhow!("No droid parked at {:?}", point))?;
+                let mut open = vec![];
+
+                for &direction in Direction::all() {
+                    let next = map.step(point, direction);
+                    if map.contains(&next) {
+                        continue;
+                    }
+
+                    let command = MovementCommand::try_from(direction)?;
+                    let mut next_droid = droid.clone();
+                    let (_, output) = next_droid.run_until_stopped(vec![command.0])?;
+                    let location = match output[..] {
+                        [0] => Location::Wall,
+                        [1] => Location::Empty,
+                        [2] => Location::Oxygen,
+                        _ => return Err(anyhow!("Unexpected droid status {:?}", output)),
+                    };
+
+                    map.insert(next, location);
+                    if location != Location::Wall {
+                        droids.insert(next, next_droid);
+                        open.push(next);
+                    }
+                }
 
-impl Iterator for Maze {
-    type Item = (Computer, TraversableLocation, Point, u64);
+                Ok(open)
+            },
+            |_| false,
+        )?;
 
-    fn next(&mut self) -> Option<(Computer, TraversableLocation, Point, u64)> {
-        if self.queue.is_empty() {
-            return None;
-        }
+        let oxygen = map
+            .iter()
+            .find(|(_, &location)| location == Location::Oxygen)
+            .map(|(point, _)| point)
+            .ok_or_else(|| anyhow!("Traversed map without finding a solution"))?;
 
-        let (current_computer, traversable_location, point, distance) =
-            self.queue.pop_front().unwrap();
-
-        for direction in vec![
-            Direction::North,
-            Direction::South,
-            Direction::West,
-            Direction::East,
-        ] {
-            let direction_input = get_direction_input(direction);
-            let direction_point = get_direction_point(direction);
-
-            if self.visited.contains_key(&point) {
-                continue;
-            }
-
-            let mut next_computer = current_computer.clone();
-            let (_, output) = next_computer.run_until_stopped(direction_input).unwrap();
-
-            let location = match output[0] {
-                0 => Location::Wall,
-                1 => Location::Traversable(TraversableLocation::Empty),
-                2 => Location::Traversable(TraversableLocation::Oxygen),
-                _ => unreachable!(),
-            };
-
-            match location {
-                Location::Traversable(tl) => {
-                    self.queue.push_back((
-                        next_computer,
-                        tl,
-                        point + direction_point,
-                        distance + 1,
-                    ));
-                }
-                _ => {}
-            }
-        }
+        Ok(Self { map, oxygen })
+    }
 
-        self.visited.insert(point, distance);
-        Some((current_computer, traversable_location, point, distance))
+    fn open_neighbors(&self, point: Point) -> Vec<Point> {
+        Direction::all()
+            .iter()
+            .map(|&direction| self.map.step(point, direction))
+            .filter(|next| self.map.get(next).is_some_and(|&l| l != Location::Wall))
+            .collect()
     }
 }
 
 pub fn part1(source: &str) -> Result<String> {
-    let maze = Maze::new(source)?;
-    for (_, location, _, distance) in maze {
-        match location {
-            TraversableLocation::Oxygen => return Ok(distance.to_string()),
-            _ => {}
-        }
-    }
-
-    Err(anyhow!("Traversed map without finding a solution"))
+    let maze = Maze::explore(source)?;
+    let search = bfs(
+        Point { x: 0, y: 0 },
+        |&point| maze.open_neighbors(point),
+        |&point| point == maze.oxygen,
+    );
+
+    let distance = search
+        .distance(&maze.oxygen)
+        .ok_or_else(|| anyhow!("Oxygen system is unreachable"))?;
+    Ok(distance.to_string())
 }
 
 pub fn part2(source: &str) -> Result<String> {
-    let (computer, _, _, _) = Maze::new(source)?
-        .find(|(_, location, _, _)| match location {
-            TraversableLocation::Oxygen => true,
-            _ => false,
-        })
-        .unwrap();
-
-    let max_dist = Maze::new_from_computer(computer)?
-        .map(|(_, _, _, distance)| distance)
-        .max_by_key(|x| *x)
-        .unwrap();
-
-    Ok((max_dist - 1).to_string())
+    let maze = Maze::explore(source)?;
+    let search = bfs(maze.oxygen, |&point| maze.open_neighbors(point), |_| false);
+
+    // Oxygen spreads one tile per minute, so filling takes as long as the
+    // path to the farthest tile.
+    let minutes = search.distances().values().max().copied().unwrap_or(0);
+    Ok(minutes.to_string())
 }
 
-fn get_direction_point(direction: Direction) -> Point {
-    match direction {
-        Direction::North => Point { x: 0, y: 1 },
-        Direction::South => Point { x: 0, y: -1 },
-        Direction::West => Point { x: -1, y: 0 },
-        Direction::East => Point { x: 1, y: 0 },
+// A droid in an east-west corridor from x = -2 to x = 3, with the oxygen
+// system at the east end.
+const CORRIDOR: &str = "\
+3,101,1008,101,4,102,1005,102,30,1008,101,3,102,1005,102,70,104,0,1105,1,0,\
+0,0,0,0,0,0,0,0,0,1007,100,3,102,1006,102,16,1001,100,1,100,1008,100,3,102,\
+1005,102,90,104,1,1105,1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1007,100,-1,102,\
+1005,102,16,1001,100,-1,100,104,1,1105,1,0,0,0,0,0,104,2,1105,1,0";
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    fn day(&self) -> u32 {
+        15
+    }
+
+    fn title(&self) -> &'static str {
+        "Oxygen System"
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        part2(input)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        const EXAMPLES: &[Example] = &[
+            Example::new(1, CORRIDOR, "3"),
+            Example::new(2, CORRIDOR, "5"),
+        ];
+        EXAMPLES
     }
 }
 
-fn get_direction_input(direction: Direction) -> Vec<i64> {
-    vec![match direction {
-        Direction::North => 1,
-        Direction::South => 2,
-        Direction::West => 3,
-        Direction::East => 4,
-    }]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explore_corridor() -> Result<()> {
+        let maze = Maze::explore(CORRIDOR)?;
+        assert_eq!(maze.oxygen, Point { x: 3, y: 0 });
+        assert_eq!(maze.map.len(), 6 + 14);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unexpected_status() {
+        // Answers every move with a status the droid never reports.
+        let error = Maze::explore("3,9,104,7,1105,1,0").unwrap_err();
+        assert!(format!("{}", error).contains("Unexpected droid status [7]"));
+    }
 }
@@ -0,0 +1,144 @@
+use crate::days::{Example, Solution};
+use anyhow::{anyhow, Result};
+
+const PHASES: usize = 100;
+const REPETITIONS: usize = 10_000;
+const BASE_PATTERN: [i64; 4] = [0, 1, 0, -1];
+
+fn parse_signal(input: &str) -> Result<Vec<i64>> {
+    input
+        .trim()
+        .chars()
+        .map(|c| {
+            c.to_digit(10)
+                .map(i64::from)
+                .ok_or_else(|| anyhow!("Invalid digit {:?} in signal", c))
+        })
+        .collect()
+}
+
+fn digits_to_string(digits: &[i64]) -> String {
+    digits.iter().map(|d| d.to_string()).collect()
+}
+
+fn phase(signal: &[i64]) -> Vec<i64> {
+    (0..signal.len())
+        .map(|row| {
+            let total: i64 = signal
+                .iter()
+                .enumerate()
+                .map(|(col, value)| value * BASE_PATTERN[((col + 1) / (row + 1)) % 4])
+                .sum();
+            total.abs() % 10
+        })
+        .collect()
+}
+
+// In the second half of the signal the pattern is all zeros up to the current
+// digit and all ones after it, so each output digit is a suffix sum.
+fn suffix_phase(signal: &mut [i64]) {
+    let mut total = 0;
+    for value in signal.iter_mut().rev() {
+        total = (total + *value) % 10;
+        *value = total;
+    }
+}
+
+pub fn part1(input: &str) -> Result<String> {
+    let mut signal = parse_signal(input)?;
+    for _ in 0..PHASES {
+        signal = phase(&signal);
+    }
+
+    Ok(digits_to_string(&signal[..8.min(signal.len())]))
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    let signal = parse_signal(input)?;
+    if signal.len() < 7 {
+        return Err(anyhow!("Signal is too short to contain a message offset"));
+    }
+
+    let offset = signal[..7].iter().fold(0, |acc, d| acc * 10 + *d as usize);
+    let length = signal.len() * REPETITIONS;
+
+    if offset + 8 > length {
+        return Err(anyhow!(
+            "Message offset {} is beyond the end of the {}-digit signal",
+            offset,
+            length
+        ));
+    }
+
+    if offset < length / 2 {
+        return Err(anyhow!(
+            "Message offset {} is in the first half of the {}-digit signal; \
+             only offsets in the second half can be decoded efficiently",
+            offset,
+            length
+        ));
+    }
+
+    let mut suffix: Vec<i64> = (offset..length)
+        .map(|idx| signal[idx % signal.len()])
+        .collect();
+
+    for _ in 0..PHASES {
+        suffix_phase(&mut suffix);
+    }
+
+    Ok(digits_to_string(&suffix[..8]))
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    fn day(&self) -> u32 {
+        16
+    }
+
+    fn title(&self) -> &'static str {
+        "Flawed Frequency Transmission"
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        part2(input)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        const EXAMPLES: &[Example] = &[
+            Example::new(1, "80871224585914546619083218645595", "24176176"),
+            Example::new(1, "19617804207202209144916044189917", "73745418"),
+            Example::new(1, "69317163492948606335995924319873", "52432133"),
+            Example::new(2, "03036732577212944063491565474664", "84462026"),
+            Example::new(2, "02935109699940807407585447034323", "78725270"),
+            Example::new(2, "03081770884921959731165446850517", "53553731"),
+        ];
+        EXAMPLES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phases() -> Result<()> {
+        let mut signal = parse_signal("12345678")?;
+        for expected in &["48226158", "34040438", "03415518", "01029498"] {
+            signal = phase(&signal);
+            assert_eq!(digits_to_string(&signal), *expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2_rejects_first_half_offset() {
+        assert!(part2("00000019617804207202209144916044189917").is_err());
+    }
+}
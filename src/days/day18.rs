@@ -0,0 +1,347 @@
+use crate::common::search::dijkstra;
+use crate::common::{Direction, Point, YAxis};
+use crate::days::{Example, Solution};
+use anyhow::{anyhow, Result};
+use std::collections::{HashSet, VecDeque};
+
+#[derive(Debug, Copy, Clone)]
+struct Route {
+    target: usize,
+    distance: usize,
+    doors: u32,
+    keys: u32,
+}
+
+#[derive(Debug, Clone)]
+struct Vault {
+    tiles: Vec<Vec<u8>>,
+    robots: Vec<Point>,
+    // Present keys in letter order, as the letter's offset from `a` and the
+    // key's position. Letters need not be contiguous.
+    keys: Vec<(u8, Point)>,
+}
+
+impl Vault {
+    fn new(input: &str) -> Result<Self> {
+        let tiles: Vec<Vec<u8>> = input
+            .lines()
+            .map(|line| line.trim().as_bytes().to_vec())
+            .filter(|row| !row.is_empty())
+            .collect();
+
+        let mut robots = vec![];
+        let mut keys = vec![None; 26];
+
+        for (y, row) in tiles.iter().enumerate() {
+            for (x, &tile) in row.iter().enumerate() {
+                let point = Point {
+                    x: x as i64,
+                    y: y as i64,
+                };
+
+                match tile {
+                    b'@' => robots.push(point),
+                    b'a'..=b'z' => {
+                        if keys[(tile - b'a') as usize].replace(point).is_some() {
+                            return Err(anyhow!("Key {} appears twice", tile as char));
+                        }
+                    }
+                    b'A'..=b'Z' | b'.' | b'#' => {}
+                    _ => return Err(anyhow!("Unexpected tile {:?} at {},{}", tile as char, x, y)),
+                }
+            }
+        }
+
+        if robots.is_empty() {
+            return Err(anyhow!("Vault has no entrance"));
+        }
+
+        let keys = (0..)
+            .zip(keys)
+            .filter_map(|(letter, key)| Some((letter, key?)))
+            .collect();
+
+        Ok(Self {
+            tiles,
+            robots,
+            keys,
+        })
+    }
+
+    fn tile(&self, point: Point) -> u8 {
+        if point.x < 0 || point.y < 0 {
+            return b'#';
+        }
+
+        self.tiles
+            .get(point.y as usize)
+            .and_then(|row| row.get(point.x as usize))
+            .copied()
+            .unwrap_or(b'#')
+    }
+
+    // Replaces a lone entrance and its surroundings with four walled-off
+    // entrances, one per quadrant.
+    fn split_entrance(&mut self) -> Result<()> {
+        if self.robots.len() == 4 {
+            return Ok(());
+        }
+
+        let center = match self.robots[..] {
+            [center] => center,
+            _ => return Err(anyhow!("Expected a single entrance to split")),
+        };
+
+        let in_bounds = |point: Point| {
+            point.x >= 0
+                && point.y >= 0
+                && self
+                    .tiles
+                    .get(point.y as usize)
+                    .is_some_and(|row| (point.x as usize) < row.len())
+        };
+        if !in_bounds(center + Point { x: -1, y: -1 }) || !in_bounds(center + Point { x: 1, y: 1 })
+        {
+            return Err(anyhow!(
+                "Entrance at {} is too close to the edge to split",
+                center
+            ));
+        }
+
+        let mut robots = vec![];
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let point = center + Point { x: dx, y: dy };
+                let tile = if dx != 0 && dy != 0 {
+                    robots.push(point);
+                    b'@'
+                } else {
+                    b'#'
+                };
+
+                self.tiles[point.y as usize][point.x as usize] = tile;
+            }
+        }
+
+        self.robots = robots;
+        Ok(())
+    }
+
+    // Nodes are the robots' starting points followed by the keys. Each route
+    // records the doors it passes through and the keys it picks up on the way.
+    fn routes(&self) -> Vec<Vec<Route>> {
+        let keys = self.keys.iter().map(|&(_, point)| point);
+        let nodes: Vec<Point> = self.robots.iter().copied().chain(keys).collect();
+
+        nodes
+            .iter()
+            .map(|&start| {
+                let mut routes = vec![];
+                let mut visited = HashSet::new();
+                let mut queue = VecDeque::new();
+
+                visited.insert(start);
+                queue.push_back((start, 0, 0u32, 0u32));
+
+                while let Some((point, distance, doors, keys)) = queue.pop_front() {
+                    for direction in Direction::all() {
+                        let next = point + direction.offset(YAxis::Down);
+                        if !visited.insert(next) {
+                            continue;
+                        }
+
+                        match self.tile(next) {
+                            b'#' => {}
+                            key @ b'a'..=b'z' => {
+                                let key = key - b'a';
+                                let index = self.key_index(key);
+                                routes.push(Route {
+                                    target: self.robots.len() + index,
+                                    distance: distance + 1,
+                                    doors,
+                                    keys,
+                                });
+
+                                let keys = keys | 1 << key;
+                                queue.push_back((next, distance + 1, doors, keys));
+                            }
+                            door @ b'A'..=b'Z' => {
+                                let doors = doors | 1 << (door - b'A');
+                                queue.push_back((next, distance + 1, doors, keys));
+                            }
+                            _ => queue.push_back((next, distance + 1, doors, keys)),
+                        }
+                    }
+                }
+
+                routes
+            })
+            .collect()
+    }
+
+    // The node index of a key among the keys, which were all found by `new`.
+    fn key_index(&self, key: u8) -> usize {
+        self.keys
+            .binary_search_by_key(&key, |&(letter, _)| letter)
+            .unwrap()
+    }
+
+    // Dijkstra over (robot positions, keys held), moving one robot at a time
+    // along a route whose doors are all unlocked.
+    fn shortest_collection(&self) -> Result<usize> {
+        let routes = self.routes();
+        let robots = self.robots.len();
+        let all_keys = self.keys.iter().fold(0u32, |all, &(key, _)| all | 1 << key);
+
+        let start = ((0..robots).collect::<Vec<_>>(), 0u32);
+        let successors = |(positions, held): &(Vec<usize>, u32)| {
+            let mut next = vec![];
+            for (robot, &node) in positions.iter().enumerate() {
+                for route in &routes[node] {
+                    let key = 1 << self.keys[route.target - robots].0;
+                    if held & key != 0 || route.doors & !held != 0 {
+                        continue;
+                    }
+
+                    let mut next_positions = positions.clone();
+                    next_positions[robot] = route.target;
+                    next.push(((next_positions, held | key | route.keys), route.distance));
+                }
+            }
+            next
+        };
+
+        let search = dijkstra(start, successors, |(_, held)| *held == all_keys);
+        search
+            .goal()
+            .and_then(|goal| search.distance(goal))
+            .ok_or_else(|| anyhow!("Unable to collect every key"))
+    }
+}
+
+pub fn part1(input: &str) -> Result<String> {
+    let vault = Vault::new(input)?;
+    Ok(vault.shortest_collection()?.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    let mut vault = Vault::new(input)?;
+    vault.split_entrance()?;
+
+    Ok(vault.shortest_collection()?.to_string())
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    fn day(&self) -> u32 {
+        18
+    }
+
+    fn title(&self) -> &'static str {
+        "Many-Worlds Interpretation"
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        part2(input)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        const EXAMPLES: &[Example] = &[
+            Example::new(1, "#########\n#b.A.@.a#\n#########", "8"),
+            Example::new(
+                1,
+                "\
+########################
+#f.D.E.e.C.b.A.@.a.B.c.#
+######################.#
+#d.....................#
+########################",
+                "86",
+            ),
+            Example::new(
+                1,
+                "\
+########################
+#...............b.C.D.f#
+#.######################
+#.....@.a.B.c.d.A.e.F.g#
+########################",
+                "132",
+            ),
+            Example::new(
+                1,
+                "\
+#################
+#i.G..c...e..H.p#
+########.########
+#j.A..b...f..D.o#
+########@########
+#k.E..a...g..B.n#
+########.########
+#l.F..d...h..C.m#
+#################",
+                "136",
+            ),
+            Example::new(
+                1,
+                "\
+########################
+#@..............ac.GI.b#
+###d#e#f################
+###A#B#C################
+###g#h#i################
+########################",
+                "81",
+            ),
+            Example::new(
+                2,
+                "\
+#######
+#a.#Cd#
+##...##
+##.@.##
+##...##
+#cB#Ab#
+#######",
+                "8",
+            ),
+            Example::new(
+                2,
+                "\
+###############
+#d.ABC.#.....a#
+######@#@######
+###############
+######@#@######
+#b.....#.....c#
+###############",
+                "24",
+            ),
+        ];
+        EXAMPLES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_letters() -> Result<()> {
+        // Without `c`, key `d` must still be collected.
+        assert_eq!(part1("#########\n#b.A.@.a#\n###d#####")?, "10");
+        assert!(part1("#######\n#a.@.a#\n#######").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_entrance_on_edge() {
+        assert!(part2("@.a\n...\n...").is_err());
+        assert!(part2("...\n...\n.a@").is_err());
+    }
+}
@@ -0,0 +1,243 @@
+use crate::common::grid::Bounds;
+use crate::common::search::bfs;
+use crate::common::{Direction, Grid, Point, YAxis};
+use crate::days::{Example, Solution};
+use anyhow::{anyhow, Result};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Copy, Clone)]
+struct Portal {
+    exit: Point,
+    // +1 for inner portals, which lead one level deeper, and -1 for outer ones.
+    level_change: i64,
+}
+
+#[derive(Debug, Clone)]
+struct Donut {
+    open: HashSet<Point>,
+    portals: HashMap<Point, Portal>,
+    start: Point,
+    end: Point,
+}
+
+impl Donut {
+    fn new(input: &str) -> Result<Self> {
+        let grid = Grid::parse(input, |c| if c == ' ' { None } else { Some(c) });
+
+        let open: HashSet<Point> = grid
+            .iter()
+            .filter(|(_, &c)| c == '.')
+            .map(|(point, _)| point)
+            .collect();
+
+        let mut maze = grid
+            .iter()
+            .filter(|(_, &c)| c == '.' || c == '#')
+            .map(|(point, _)| point);
+        let first = maze
+            .next()
+            .ok_or_else(|| anyhow!("The maze has no tiles"))?;
+        let maze = maze.fold(Bounds::around(first), |maze, point| maze.including(point));
+
+        // Each label is two letters read left-to-right or top-to-bottom, with
+        // the portal itself on the open tile next to one of the letters.
+        let mut labels: HashMap<String, Vec<Point>> = HashMap::new();
+        for (point, &c) in grid.iter().filter(|(_, c)| c.is_ascii_uppercase()) {
+            for direction in Direction::all() {
+                let offset = direction.offset(YAxis::Down);
+                let tile = point + offset;
+                if !open.contains(&tile) {
+                    continue;
+                }
+
+                let other_point = point - offset;
+                let other = match grid.get(&other_point) {
+                    Some(&other) if other.is_ascii_uppercase() => other,
+                    _ => return Err(anyhow!("Incomplete portal label at {:?}", point)),
+                };
+
+                let label = if (other_point.y, other_point.x) < (point.y, point.x) {
+                    format!("{}{}", other, c)
+                } else {
+                    format!("{}{}", c, other)
+                };
+
+                labels.entry(label).or_default().push(tile);
+            }
+        }
+
+        let mut endpoint = |label: &str| match labels.remove(label).as_deref() {
+            Some(&[point]) => Ok(point),
+            _ => Err(anyhow!("Expected exactly one {} tile", label)),
+        };
+        let start = endpoint("AA")?;
+        let end = endpoint("ZZ")?;
+
+        let is_outer = |p: &Point| {
+            p.x == maze.min.x || p.x == maze.max.x || p.y == maze.min.y || p.y == maze.max.y
+        };
+        let mut portals = HashMap::new();
+        for (label, tiles) in labels {
+            let (a, b) = match tiles[..] {
+                [a, b] => (a, b),
+                _ => return Err(anyhow!("Portal {} has {} ends", label, tiles.len())),
+            };
+
+            for &(from, to) in &[(a, b), (b, a)] {
+                let level_change = if is_outer(&from) { -1 } else { 1 };
+                portals.insert(
+                    from,
+                    Portal {
+                        exit: to,
+                        level_change,
+                    },
+                );
+            }
+        }
+
+        Ok(Self {
+            open,
+            portals,
+            start,
+            end,
+        })
+    }
+
+    // Breadth-first search over (tile, level). Without recursion every portal
+    // keeps the level at zero. With recursion, outer portals are walls on the
+    // outermost level, and no shortest path goes deeper than one level per
+    // pair of an inner and an outer portal. On the way down to its deepest
+    // level, a path enters each level k for the last time through an inner
+    // portal, and on the way back leaves it for the first time through an
+    // outer one. If two levels shared the same pair of portals, cutting out
+    // the stretch between them would give a shorter path, so every level
+    // has its own pair.
+    fn shortest_path(&self, recursive: bool) -> Result<u64> {
+        let inner = self.portals.values().filter(|p| p.level_change > 0).count();
+        let outer = self.portals.len() - inner;
+        let max_level = (inner * outer) as i64;
+
+        let successors = |&(point, level): &(Point, i64)| {
+            let walks = Direction::all()
+                .iter()
+                .map(move |direction| (point + direction.offset(YAxis::Down), level))
+                .filter(|(next, _)| self.open.contains(next));
+
+            let warp = self.portals.get(&point).and_then(|portal| {
+                let next_level = if recursive {
+                    level + portal.level_change
+                } else {
+                    level
+                };
+
+                if (0..=max_level).contains(&next_level) {
+                    Some((portal.exit, next_level))
+                } else {
+                    None
+                }
+            });
+
+            walks.chain(warp).collect::<Vec<_>>()
+        };
+
+        let goal = (self.end, 0);
+        bfs((self.start, 0), successors, |&node| node == goal)
+            .distance(&goal)
+            .ok_or_else(|| anyhow!("No path from AA to ZZ"))
+    }
+}
+
+pub fn part1(input: &str) -> Result<String> {
+    Ok(Donut::new(input)?.shortest_path(false)?.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    Ok(Donut::new(input)?.shortest_path(true)?.to_string())
+}
+
+const EXAMPLE: &str = "         A
+         A
+  #######.#########
+  #######.........#
+  #######.#######.#
+  #######.#######.#
+  #######.#######.#
+  #####  B    ###.#
+BC...##  C    ###.#
+  ##.##       ###.#
+  ##...DE  F  ###.#
+  #####    G  ###.#
+  #########.#####.#
+DE..#######...###.#
+  #.#########.###.#
+FG..#########.....#
+  ###########.#####
+             Z
+             Z       ";
+
+const RECURSIVE_EXAMPLE: &str = "             Z L X W       C                 
+             Z P Q B       K                 
+  ###########.#.#.#.#######.###############  
+  #...#.......#.#.......#.#.......#.#.#...#  
+  ###.#.#.#.#.#.#.#.###.#.#.#######.#.#.###  
+  #.#...#.#.#...#.#.#...#...#...#.#.......#  
+  #.###.#######.###.###.#.###.###.#.#######  
+  #...#.......#.#...#...#.............#...#  
+  #.#########.#######.#.#######.#######.###  
+  #...#.#    F       R I       Z    #.#.#.#  
+  #.###.#    D       E C       H    #.#.#.#  
+  #.#...#                           #...#.#  
+  #.###.#                           #.###.#  
+  #.#....OA                       WB..#.#..ZH
+  #.###.#                           #.#.#.#  
+CJ......#                           #.....#  
+  #######                           #######  
+  #.#....CK                         #......IC
+  #.###.#                           #.###.#  
+  #.....#                           #...#.#  
+  ###.###                           #.#.#.#  
+XF....#.#                         RF..#.#.#  
+  #####.#                           #######  
+  #......CJ                       NM..#...#  
+  ###.#.#                           #.###.#  
+RE....#.#                           #......RF
+  ###.###        X   X       L      #.#.#.#  
+  #.....#        F   Q       P      #.#.#.#  
+  ###.###########.###.#######.#########.###  
+  #.....#...#.....#.......#...#.....#.#...#  
+  #####.#.###.#######.#######.###.###.#.#.#  
+  #.......#.......#.#.#.#.#...#...#...#.#.#  
+  #####.###.#####.#.#.#.#.###.###.#.###.###  
+  #.......#.....#.#...#...............#...#  
+  #############.#.#.###.###################  
+               A O F   N                     
+               A A D   M                     ";
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    fn day(&self) -> u32 {
+        20
+    }
+
+    fn title(&self) -> &'static str {
+        "Donut Maze"
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        part2(input)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        const EXAMPLES: &[Example] = &[
+            Example::new(1, EXAMPLE, "23"),
+            Example::new(2, EXAMPLE, "26"),
+            Example::new(2, RECURSIVE_EXAMPLE, "396"),
+        ];
+        EXAMPLES
+    }
+}
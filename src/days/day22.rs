@@ -0,0 +1,270 @@
+use crate::common::math::mod_inverse;
+use crate::days::{Example, Solution};
+use anyhow::{anyhow, Context, Result};
+
+const SMALL_DECK: i128 = 10_007;
+const LARGE_DECK: i128 = 119_315_717_514_047;
+const REPETITIONS: u128 = 101_741_582_076_661;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Technique {
+    NewStack,
+    Cut(i128),
+    Increment(i128),
+}
+
+impl Technique {
+    fn parse(line: &str) -> Result<Self> {
+        let line = line.trim();
+
+        if line == "deal into new stack" {
+            Ok(Technique::NewStack)
+        } else if let Some(n) = line.strip_prefix("cut ") {
+            Ok(Technique::Cut(n.parse()?))
+        } else if let Some(n) = line.strip_prefix("deal with increment ") {
+            Ok(Technique::Increment(n.parse()?))
+        } else {
+            Err(anyhow!("Unrecognized technique"))
+        }
+    }
+
+    fn to_affine(self, deck_size: i128) -> Affine {
+        let (a, b) = match self {
+            Technique::NewStack => (-1, -1),
+            Technique::Cut(n) => (1, -n),
+            Technique::Increment(n) => (n, 0),
+        };
+
+        Affine::new(a, b, deck_size)
+    }
+
+    // Literal simulation on an explicit deck, used to cross-check the algebra.
+    #[cfg(test)]
+    fn apply(self, deck: &[u64]) -> Vec<u64> {
+        let size = deck.len();
+
+        match self {
+            Technique::NewStack => deck.iter().rev().copied().collect(),
+            Technique::Cut(n) => {
+                let n = n.rem_euclid(size as i128) as usize;
+                deck[n..].iter().chain(&deck[..n]).copied().collect()
+            }
+            Technique::Increment(n) => {
+                let mut result = vec![0; size];
+                for (idx, &card) in deck.iter().enumerate() {
+                    result[(idx * n as usize) % size] = card;
+                }
+                result
+            }
+        }
+    }
+}
+
+fn parse_techniques(input: &str) -> Result<Vec<Technique>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            Technique::parse(line)
+                .with_context(|| format!("Invalid technique on line {}: {:?}", idx + 1, line))
+        })
+        .collect()
+}
+
+// The map x -> a * x + b (mod m), sending a card's position before a shuffle
+// to its position after it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Affine {
+    a: i128,
+    b: i128,
+    m: i128,
+}
+
+impl Affine {
+    fn new(a: i128, b: i128, m: i128) -> Self {
+        Self {
+            a: a.rem_euclid(m),
+            b: b.rem_euclid(m),
+            m,
+        }
+    }
+
+    fn identity(m: i128) -> Self {
+        Self::new(1, 0, m)
+    }
+
+    // Applies `self` first, then `next`.
+    fn then(self, next: Affine) -> Self {
+        Self::new(next.a * self.a, next.a * self.b + next.b, self.m)
+    }
+
+    fn apply(self, x: i128) -> i128 {
+        (self.a * x + self.b).rem_euclid(self.m)
+    }
+
+    // Composes the map with itself `n` times by repeated squaring.
+    fn pow(self, n: u128) -> Self {
+        let mut result = Self::identity(self.m);
+        let mut base = self;
+        let mut n = n;
+
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.then(base);
+            }
+            base = base.then(base);
+            n >>= 1;
+        }
+
+        result
+    }
+
+    fn inverse(self) -> Result<Self> {
+        let a_inverse = mod_inverse(self.a, self.m)
+            .ok_or_else(|| anyhow!("Shuffle is not invertible for a deck of {}", self.m))?;
+
+        Ok(Self::new(a_inverse, -a_inverse * self.b, self.m))
+    }
+}
+
+fn shuffle(techniques: &[Technique], deck_size: i128) -> Affine {
+    techniques
+        .iter()
+        .fold(Affine::identity(deck_size), |acc, technique| {
+            acc.then(technique.to_affine(deck_size))
+        })
+}
+
+// Lists the cards from top to bottom after one shuffle of a small deck.
+fn deck_order(input: &str, deck_size: usize) -> Result<String> {
+    let techniques = parse_techniques(input)?;
+    let inverse = shuffle(&techniques, deck_size as i128).inverse()?;
+
+    let cards: Vec<String> = (0..deck_size as i128)
+        .map(|position| inverse.apply(position).to_string())
+        .collect();
+    Ok(cards.join(" "))
+}
+
+pub fn part1(input: &str) -> Result<String> {
+    let techniques = parse_techniques(input)?;
+    Ok(shuffle(&techniques, SMALL_DECK).apply(2019).to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    let techniques = parse_techniques(input)?;
+    let repeated = shuffle(&techniques, LARGE_DECK).pow(REPETITIONS);
+
+    // The inverse map takes a final position back to the card that lands there.
+    Ok(repeated.inverse()?.apply(2020).to_string())
+}
+
+const LAST_EXAMPLE: &str = "\
+deal into new stack\ncut -2\ndeal with increment 7\ncut 8\ncut -4\n\
+deal with increment 7\ncut 3\ndeal with increment 9\ndeal with increment 3\ncut -1";
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    fn day(&self) -> u32 {
+        22
+    }
+
+    fn title(&self) -> &'static str {
+        "Slam Shuffle"
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        part2(input)
+    }
+
+    // The examples shuffle a deck of ten cards, given as the setting, and
+    // list the resulting deck.
+    fn examples(&self) -> &'static [Example] {
+        const EXAMPLES: &[Example] = &[
+            Example::new(
+                1,
+                "deal with increment 7\ndeal into new stack\ndeal into new stack",
+                "0 3 6 9 2 5 8 1 4 7",
+            )
+            .with_setting(10),
+            Example::new(
+                1,
+                "cut 6\ndeal with increment 7\ndeal into new stack",
+                "3 0 7 4 1 8 5 2 9 6",
+            )
+            .with_setting(10),
+            Example::new(
+                1,
+                "deal with increment 7\ndeal with increment 9\ncut -2",
+                "6 3 0 7 4 1 8 5 2 9",
+            )
+            .with_setting(10),
+            Example::new(1, LAST_EXAMPLE, "9 2 5 8 1 4 7 0 3 6").with_setting(10),
+        ];
+        EXAMPLES
+    }
+
+    fn run_example(&self, example: &Example) -> Result<String> {
+        match example.setting {
+            Some(deck_size) => deck_order(example.input, deck_size),
+            None => self.run(example.part, example.input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn simulate(techniques: &[Technique], deck_size: usize) -> Vec<u64> {
+        techniques
+            .iter()
+            .fold((0..deck_size as u64).collect(), |deck: Vec<u64>, t| {
+                t.apply(&deck)
+            })
+    }
+
+    #[test]
+    fn test_examples_match_simulation() -> Result<()> {
+        for example in Day22.examples() {
+            let techniques = parse_techniques(example.input)?;
+            let deck = simulate(&techniques, example.setting.unwrap_or(10));
+            let listed: Vec<String> = deck.iter().map(u64::to_string).collect();
+
+            assert_eq!(listed.join(" "), example.answer);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_repeated_shuffle_matches_simulation() -> Result<()> {
+        let techniques = parse_techniques(LAST_EXAMPLE)?;
+
+        let mut deck: Vec<u64> = (0..SMALL_DECK as u64).collect();
+        for repetitions in 1..=5 {
+            deck = techniques.iter().fold(deck, |deck, t| t.apply(&deck));
+
+            let inverse = shuffle(&techniques, SMALL_DECK)
+                .pow(repetitions)
+                .inverse()?;
+            for position in &[0, 1, 2020, 10_006] {
+                assert_eq!(deck[*position], inverse.apply(*position as i128) as u64);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error_reports_line() {
+        let error = parse_techniques("cut 3\ndeal with 7").unwrap_err();
+        assert!(format!("{}", error).contains("line 2"));
+    }
+}
@@ -0,0 +1,240 @@
+use crate::days::{no_steps, Example, Solution};
+use anyhow::{anyhow, Result};
+use std::collections::{HashMap, HashSet};
+
+const SIZE: usize = 5;
+const CENTER: usize = 12;
+const MINUTES: usize = 200;
+
+// Bit `y * 5 + x` is set when the tile at (x, y) has a bug.
+type Layout = u32;
+
+fn parse_layout(input: &str) -> Result<Layout> {
+    let rows: Vec<&str> = input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    if rows.len() != SIZE || rows.iter().any(|row| row.len() != SIZE) {
+        return Err(anyhow!("Expected a {}x{} grid", SIZE, SIZE));
+    }
+
+    let mut layout = 0;
+    for (idx, c) in rows.iter().flat_map(|row| row.chars()).enumerate() {
+        match c {
+            '#' => layout |= 1 << idx,
+            '.' | '?' => {}
+            _ => return Err(anyhow!("Unexpected tile {:?}", c)),
+        }
+    }
+
+    Ok(layout)
+}
+
+fn next_state(alive: bool, neighbors: u32) -> bool {
+    neighbors == 1 || (!alive && neighbors == 2)
+}
+
+fn flat_neighbor_masks() -> Vec<Layout> {
+    (0..SIZE * SIZE)
+        .map(|idx| {
+            let (x, y) = (idx % SIZE, idx / SIZE);
+            let mut mask = 0;
+            if x > 0 {
+                mask |= 1 << (idx - 1);
+            }
+            if x < SIZE - 1 {
+                mask |= 1 << (idx + 1);
+            }
+            if y > 0 {
+                mask |= 1 << (idx - SIZE);
+            }
+            if y < SIZE - 1 {
+                mask |= 1 << (idx + SIZE);
+            }
+            mask
+        })
+        .collect()
+}
+
+fn step(layout: Layout, masks: &[Layout]) -> Layout {
+    (0..SIZE * SIZE)
+        .filter(|&idx| next_state(layout & 1 << idx != 0, (layout & masks[idx]).count_ones()))
+        .fold(0, |acc, idx| acc | 1 << idx)
+}
+
+// Neighbor masks for a tile in the recursive grid, split by level: the
+// enclosing grid (one level up), the same grid, and the nested grid.
+#[derive(Debug, Copy, Clone, Default)]
+struct RecursiveMask {
+    outer: Layout,
+    same: Layout,
+    inner: Layout,
+}
+
+fn recursive_neighbor_masks() -> Vec<RecursiveMask> {
+    let flat = flat_neighbor_masks();
+    let row = |y: usize| (0..SIZE).fold(0, |acc, x| acc | 1 << (y * SIZE + x));
+    let column = |x: usize| (0..SIZE).fold(0, |acc, y| acc | 1 << (y * SIZE + x));
+
+    (0..SIZE * SIZE)
+        .map(|idx| {
+            if idx == CENTER {
+                return RecursiveMask::default();
+            }
+
+            let (x, y) = (idx % SIZE, idx / SIZE);
+            let mut mask = RecursiveMask {
+                same: flat[idx] & !(1 << CENTER),
+                ..RecursiveMask::default()
+            };
+
+            // Edges of this grid border the tiles around the enclosing center.
+            if y == 0 {
+                mask.outer |= 1 << (CENTER - SIZE);
+            }
+            if y == SIZE - 1 {
+                mask.outer |= 1 << (CENTER + SIZE);
+            }
+            if x == 0 {
+                mask.outer |= 1 << (CENTER - 1);
+            }
+            if x == SIZE - 1 {
+                mask.outer |= 1 << (CENTER + 1);
+            }
+
+            // Tiles around the center border a whole edge of the nested grid.
+            match idx {
+                i if i == CENTER - SIZE => mask.inner = row(0),
+                i if i == CENTER + SIZE => mask.inner = row(SIZE - 1),
+                i if i == CENTER - 1 => mask.inner = column(0),
+                i if i == CENTER + 1 => mask.inner = column(SIZE - 1),
+                _ => {}
+            }
+
+            mask
+        })
+        .collect()
+}
+
+fn recursive_step(levels: &HashMap<i64, Layout>, masks: &[RecursiveMask]) -> HashMap<i64, Layout> {
+    let min = levels.keys().min().copied().unwrap_or(0) - 1;
+    let max = levels.keys().max().copied().unwrap_or(0) + 1;
+    let get = |level: i64| levels.get(&level).copied().unwrap_or(0);
+
+    (min..=max)
+        .map(|level| {
+            let (outer, layout, inner) = (get(level - 1), get(level), get(level + 1));
+            let next = (0..SIZE * SIZE)
+                .filter(|&idx| idx != CENTER)
+                .filter(|&idx| {
+                    let mask = masks[idx];
+                    let neighbors = (outer & mask.outer).count_ones()
+                        + (layout & mask.same).count_ones()
+                        + (inner & mask.inner).count_ones();
+                    next_state(layout & 1 << idx != 0, neighbors)
+                })
+                .fold(0, |acc, idx| acc | 1 << idx);
+
+            (level, next)
+        })
+        .filter(|&(_, layout)| layout != 0)
+        .collect()
+}
+
+pub fn bugs_after(input: &str, minutes: usize) -> Result<String> {
+    let masks = recursive_neighbor_masks();
+    let mut levels = HashMap::new();
+    levels.insert(0, parse_layout(input)? & !(1 << CENTER));
+
+    for _ in 0..minutes {
+        levels = recursive_step(&levels, &masks);
+    }
+
+    Ok(levels
+        .values()
+        .map(|layout| layout.count_ones())
+        .sum::<u32>()
+        .to_string())
+}
+
+pub fn part1(input: &str) -> Result<String> {
+    let masks = flat_neighbor_masks();
+    let mut layout = parse_layout(input)?;
+    let mut seen = HashSet::new();
+
+    // With tile i worth 2^i, the biodiversity rating is the layout itself.
+    while seen.insert(layout) {
+        layout = step(layout, &masks);
+    }
+
+    Ok(layout.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    bugs_after(input, MINUTES)
+}
+
+const EXAMPLE: &str = "\
+....#
+#..#.
+#..##
+..#..
+#....";
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    fn day(&self) -> u32 {
+        24
+    }
+
+    fn title(&self) -> &'static str {
+        "Planet of Discord"
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        part2(input)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        const EXAMPLES: &[Example] = &[
+            Example::new(1, EXAMPLE, "2129920"),
+            Example::new(2, EXAMPLE, "99").with_setting(10),
+        ];
+        EXAMPLES
+    }
+
+    fn run_with_steps(&self, part: u32, input: &str, steps: usize) -> Result<String> {
+        match part {
+            2 => bugs_after(input, steps),
+            _ => Err(no_steps(self.day(), part)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_step() -> Result<()> {
+        let after = "\
+#..#.
+####.
+###.#
+##.##
+.##..";
+
+        assert_eq!(
+            step(parse_layout(EXAMPLE)?, &flat_neighbor_masks()),
+            parse_layout(after)?
+        );
+        Ok(())
+    }
+}
@@ -1,7 +1,164 @@
+use anyhow::{anyhow, Result};
+
 pub mod day01;
 pub mod day02;
 pub mod day05;
 pub mod day09;
+pub mod day10;
 pub mod day11;
+pub mod day12;
 pub mod day13;
+pub mod day14;
 pub mod day15;
+pub mod day16;
+pub mod day18;
+pub mod day20;
+pub mod day22;
+pub mod day24;
+
+// A published example input together with the answer it should produce.
+// Some examples are published with a different setting than the puzzle
+// itself, such as a step count or deck size; what it means is up to the day.
+#[derive(Debug, Copy, Clone)]
+pub struct Example {
+    pub part: u32,
+    pub input: &'static str,
+    pub answer: &'static str,
+    pub setting: Option<usize>,
+}
+
+impl Example {
+    pub const fn new(part: u32, input: &'static str, answer: &'static str) -> Self {
+        Example {
+            part,
+            input,
+            answer,
+            setting: None,
+        }
+    }
+
+    pub const fn with_setting(self, setting: usize) -> Self {
+        Example {
+            setting: Some(setting),
+            ..self
+        }
+    }
+}
+
+pub(crate) fn no_steps(day: u32, part: u32) -> anyhow::Error {
+    anyhow!("Question {} part {} does not take a step count", day, part)
+}
+
+pub trait Solution: Sync {
+    fn day(&self) -> u32;
+
+    fn title(&self) -> &'static str;
+
+    fn part1(&self, input: &str) -> Result<String>;
+
+    fn part2(&self, input: &str) -> Result<String>;
+
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    // Days that simulate for a fixed number of steps override this to let the
+    // CLI reproduce examples with a different step count.
+    fn run_with_steps(&self, part: u32, _input: &str, _steps: usize) -> Result<String> {
+        Err(no_steps(self.day(), part))
+    }
+
+    // Examples published with a setting are run with it as the step count;
+    // days whose examples need something else override this.
+    fn run_example(&self, example: &Example) -> Result<String> {
+        match example.setting {
+            Some(steps) => self.run_with_steps(example.part, example.input, steps),
+            None => self.run(example.part, example.input),
+        }
+    }
+
+    fn run(&self, part: u32, input: &str) -> Result<String> {
+        match part {
+            1 => self.part1(input),
+            2 => self.part2(input),
+            _ => Err(anyhow!("Part {} not implemented", part)),
+        }
+    }
+}
+
+// Every implemented day, in order. Adding a day only requires a `pub mod`
+// declaration above and an entry here.
+pub const SOLUTIONS: &[&dyn Solution] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day05::Day05,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day18::Day18,
+    &day20::Day20,
+    &day22::Day22,
+    &day24::Day24,
+];
+
+pub fn get_solution(day: u32) -> Result<&'static dyn Solution> {
+    SOLUTIONS
+        .iter()
+        .find(|solution| solution.day() == day)
+        .copied()
+        .ok_or_else(|| {
+            let implemented: Vec<String> = SOLUTIONS
+                .iter()
+                .map(|solution| solution.day().to_string())
+                .collect();
+
+            anyhow!(
+                "Question {} not implemented; implemented days are {}",
+                day,
+                implemented.join(", ")
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_ordered() {
+        let days: Vec<u32> = SOLUTIONS.iter().map(|solution| solution.day()).collect();
+        let mut sorted = days.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        assert_eq!(days, sorted);
+    }
+
+    #[test]
+    fn test_examples() -> Result<()> {
+        for solution in SOLUTIONS {
+            for example in solution.examples() {
+                assert_eq!(
+                    solution.run_example(example)?,
+                    example.answer,
+                    "day {} part {} example",
+                    solution.day(),
+                    example.part
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unimplemented_day_lists_days() {
+        let error = get_solution(3).err().unwrap();
+        assert!(format!("{}", error).contains("1, 2, 5"));
+    }
+}
@@ -0,0 +1,195 @@
+use anyhow::{anyhow, Context, Result};
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+const CACHE_ENV: &str = "AOC2019_INPUT_CACHE";
+
+// Converts CRLF line endings to LF, drops a leading byte order mark and
+// collapses trailing blank lines into a single newline. Whitespace within and
+// at the end of lines is preserved, since some puzzles depend on it.
+pub fn normalize(raw: &str) -> String {
+    let text = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+    let mut normalized = text.replace("\r\n", "\n");
+
+    let trimmed = normalized.trim_end_matches('\n').len();
+    normalized.truncate(trimmed);
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+
+    normalized
+}
+
+pub fn read_file(path: &Path) -> Result<String> {
+    let raw = fs::read_to_string(path)
+        .with_context(|| format!("Unable to read input file {}", path.display()))?;
+
+    Ok(normalize(&raw))
+}
+
+pub fn read_stdin() -> Result<String> {
+    read_stdin_if_provided()?.ok_or_else(|| anyhow!("No input was provided on stdin"))
+}
+
+// Like `read_stdin`, but treats empty input, such as `/dev/null` or a closed
+// pipe, as no input rather than an error.
+pub fn read_stdin_if_provided() -> Result<Option<String>> {
+    let mut raw = String::new();
+    std::io::stdin().read_to_string(&mut raw)?;
+
+    let input = normalize(&raw);
+    Ok(Some(input).filter(|input| !input.is_empty()))
+}
+
+fn default_cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os(CACHE_ENV) {
+        return Some(PathBuf::from(dir));
+    }
+
+    env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .map(|dir| dir.join("aoc2019").join("inputs"))
+}
+
+// Puzzle inputs are looked up by day, first in the project's inputs directory
+// and then in an offline cache directory, both using `qNN.txt` file names.
+#[derive(Debug, Clone)]
+pub struct InputStore {
+    inputs_dir: PathBuf,
+    cache_dir: Option<PathBuf>,
+}
+
+impl InputStore {
+    pub fn new(inputs_dir: PathBuf, cache_dir: Option<PathBuf>) -> Self {
+        Self {
+            inputs_dir,
+            cache_dir: cache_dir.or_else(default_cache_dir),
+        }
+    }
+
+    // A store that only reads `inputs_dir`, ignoring the environment and any
+    // user cache, for tests and benchmarks that must be reproducible.
+    pub fn local(inputs_dir: PathBuf) -> Self {
+        Self {
+            inputs_dir,
+            cache_dir: None,
+        }
+    }
+
+    fn file_name(day: u32) -> String {
+        format!("q{:02}.txt", day)
+    }
+
+    pub fn candidates(&self, day: u32) -> Vec<PathBuf> {
+        let file_name = Self::file_name(day);
+
+        std::iter::once(&self.inputs_dir)
+            .chain(self.cache_dir.as_ref())
+            .map(|dir| dir.join(&file_name))
+            .collect()
+    }
+
+    pub fn find(&self, day: u32) -> Option<PathBuf> {
+        self.candidates(day).into_iter().find(|path| path.is_file())
+    }
+
+    pub fn load(&self, day: u32) -> Result<String> {
+        match self.find(day) {
+            Some(path) => read_file(&path),
+            None => {
+                let searched: Vec<String> = self
+                    .candidates(day)
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect();
+
+                Err(anyhow!(
+                    "No input found for day {} (looked in {}); pass --input <path>, \
+                     pipe the input on stdin, or add it with `aoc2019 cache {} --input <path>`",
+                    day,
+                    searched.join(", "),
+                    day
+                ))
+            }
+        }
+    }
+
+    pub fn store(&self, day: u32, contents: &str) -> Result<PathBuf> {
+        let dir = self
+            .cache_dir
+            .as_ref()
+            .ok_or_else(|| anyhow!("No cache directory; pass --cache-dir or set {}", CACHE_ENV))?;
+
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Unable to create cache directory {}", dir.display()))?;
+
+        let path = dir.join(Self::file_name(day));
+        fs::write(&path, normalize(contents))
+            .with_context(|| format!("Unable to write {}", path.display()))?;
+
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc2019-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("1\r\n2\r\n\r\n"), "1\n2\n");
+        assert_eq!(normalize("\u{feff}1,2,3"), "1,2,3\n");
+        assert_eq!(normalize("  A  \n  #.# \n"), "  A  \n  #.# \n");
+        assert_eq!(normalize("\r\n\n"), "");
+    }
+
+    #[test]
+    fn test_lookup_order() -> Result<()> {
+        let inputs = temp_dir("inputs");
+        let cache = temp_dir("cache");
+        let store = InputStore::new(inputs.clone(), Some(cache.clone()));
+
+        assert!(store.load(1).is_err());
+
+        store.store(1, "cached\r\n")?;
+        assert_eq!(store.load(1)?, "cached\n");
+
+        fs::write(inputs.join("q01.txt"), "local")?;
+        assert_eq!(store.load(1)?, "local\n");
+
+        fs::remove_dir_all(inputs)?;
+        fs::remove_dir_all(cache)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_input_lists_paths() -> Result<()> {
+        let inputs = temp_dir("missing");
+        let store = InputStore::local(inputs.clone());
+        let error = store.load(7).unwrap_err();
+
+        let expected = inputs.join("q07.txt");
+        assert!(format!("{}", error).contains(&*expected.to_string_lossy()));
+
+        fs::remove_dir_all(inputs)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_local_store_skips_cache() {
+        let store = InputStore::local(PathBuf::from("inputs"));
+
+        assert_eq!(store.candidates(3), vec![PathBuf::from("inputs/q03.txt")]);
+        assert!(store.store(3, "input").is_err());
+    }
+}
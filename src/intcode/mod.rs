@@ -1,5 +1,28 @@
+//! The Intcode virtual machine shared by the Intcode puzzles.
+//!
+//! A [`Computer`] runs until it halts or needs input it has not been given,
+//! so interactive programs are driven by calling
+//! [`run_until_stopped`](Computer::run_until_stopped) repeatedly:
+//!
+//! ```
+//! use aoc2019::intcode::{Computer, StoppedResult};
+//!
+//! // Echoes each input until it receives a zero.
+//! let mut computer = Computer::new(&[3, 9, 4, 9, 1005, 9, 0, 99, 0, 0]);
+//! assert_eq!(computer.run_until_stopped(vec![7])?, (StoppedResult::Blocked, vec![7]));
+//! assert_eq!(computer.run_until_stopped(vec![0])?, (StoppedResult::Halted, vec![0]));
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
 use anyhow::{anyhow, Result};
 
+/// Upper bound on addressable memory. Programs may address memory beyond
+/// their initial image, which reads as zero and grows on write; addresses
+/// past this limit are errors rather than unbounded allocations.
+pub const MAX_MEMORY: usize = 1 << 20;
+
+const INITIAL_MEMORY: usize = 4096;
+
 #[derive(Debug, Copy, Clone)]
 enum Instruction {
     Add(Parameter, Parameter, Parameter),
@@ -20,17 +43,24 @@ enum Parameter {
     Indexed(IndexedParameter),
 }
 
+/// A memory address, either absolute or relative to the relative base.
 #[derive(Debug, Copy, Clone)]
 pub enum IndexedParameter {
     Positional(usize),
-    Relative(usize),
+    Relative(i64),
 }
 
+/// An Intcode machine: memory, instruction pointer and relative base.
+///
+/// Cloning a computer snapshots its whole state, so a clone resumes exactly
+/// where the original stopped.
 #[derive(Debug, Clone)]
 pub struct Computer {
     memory: Vec<i64>,
     pointer: usize,
-    relative_base: usize,
+    relative_base: i64,
+    executed: u64,
+    instruction_limit: Option<u64>,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -39,34 +69,76 @@ enum ExecutionResult {
     Stopped(StoppedResult),
 }
 
-#[derive(Debug, Copy, Clone)]
+/// Why [`Computer::run_until_stopped`] returned.
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum StoppedResult {
     Blocked,
     Halted,
 }
 
 impl Computer {
-    pub fn new(initial_memory: &Vec<i64>) -> Computer {
-        let mut internal_memory = initial_memory.clone();
-        internal_memory.resize(4096, 0);
+    /// Creates a computer with `initial_memory` loaded at address zero.
+    pub fn new(initial_memory: &[i64]) -> Computer {
+        let mut internal_memory = initial_memory.to_vec();
+        if internal_memory.len() < INITIAL_MEMORY {
+            internal_memory.resize(INITIAL_MEMORY, 0);
+        }
 
         Computer {
             memory: internal_memory,
             pointer: 0,
             relative_base: 0,
+            executed: 0,
+            instruction_limit: None,
         }
     }
 
+    /// Parses a comma-separated program, as found in puzzle inputs.
+    ///
+    /// ```
+    /// use aoc2019::intcode::Computer;
+    ///
+    /// let mut computer = Computer::new_from_str("1,9,10,3,2,3,11,0,99,30,40,50\n")?;
+    /// computer.run(vec![])?;
+    /// assert_eq!(computer.get_memory_value(0), 3500);
+    ///
+    /// assert!(Computer::new_from_str("1,2,x").is_err());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
     pub fn new_from_str(serialized_memory: &str) -> Result<Computer> {
         let initial_memory = serialized_memory
             .trim()
             .split(',')
-            .map(|x| x.parse::<i64>())
+            .map(|x| x.trim().parse::<i64>())
             .collect::<Result<Vec<_>, _>>()?;
 
+        if initial_memory.len() > MAX_MEMORY {
+            return Err(anyhow!(
+                "Program of {} values exceeds the memory limit",
+                initial_memory.len()
+            ));
+        }
+
         Ok(Self::new(&initial_memory))
     }
 
+    /// Caps the total number of instructions executed over the computer's
+    /// lifetime, so untrusted programs cannot loop forever. Exceeding the
+    /// limit is an error.
+    ///
+    /// ```
+    /// use aoc2019::intcode::Computer;
+    ///
+    /// let mut computer = Computer::new(&[1105, 1, 0]);
+    /// computer.set_instruction_limit(Some(1000));
+    /// assert!(computer.run(vec![]).is_err());
+    /// ```
+    pub fn set_instruction_limit(&mut self, limit: Option<u64>) {
+        self.instruction_limit = limit;
+    }
+
+    /// Runs to completion with the given input, returning all output. Running
+    /// out of input is an error.
     pub fn run(&mut self, input: Vec<i64>) -> Result<Vec<i64>> {
         let (result, output) = self.run_until_stopped(input)?;
         match result {
@@ -75,13 +147,47 @@ impl Computer {
         }
     }
 
+    /// Runs until the program halts or needs more input than it was given,
+    /// returning the output produced along the way.
     pub fn run_until_stopped(&mut self, input: Vec<i64>) -> Result<(StoppedResult, Vec<i64>)> {
-        let mut input_stream = input.into_iter();
+        self.run_until_stopped_from(&mut input.into_iter())
+    }
+
+    /// Like [`run_until_stopped`](Self::run_until_stopped), but reads input
+    /// from an iterator and leaves whatever the program did not read in it.
+    ///
+    /// ```
+    /// use aoc2019::intcode::{Computer, StoppedResult};
+    ///
+    /// // Reads one input, then halts.
+    /// let mut computer = Computer::new(&[3, 3, 99, 0]);
+    /// let mut input = vec![1, 2, 3].into_iter();
+    /// let (result, _) = computer.run_until_stopped_from(&mut input)?;
+    /// assert_eq!(result, StoppedResult::Halted);
+    /// assert_eq!(input.len(), 2);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn run_until_stopped_from<I>(&mut self, input: &mut I) -> Result<(StoppedResult, Vec<i64>)>
+    where
+        I: Iterator<Item = i64>,
+    {
         let mut output = vec![];
 
         loop {
+            if let Some(limit) = self.instruction_limit {
+                if self.executed >= limit {
+                    return Err(anyhow!("Exceeded the limit of {} instructions", limit));
+                }
+            }
+
             let instruction = self.get_instruction()?;
-            let result = self.do_instruction(instruction, &mut input_stream);
+            let result = self.do_instruction(instruction, &mut *input)?;
+
+            // An input instruction that blocks is retried on resume, so it only
+            // counts towards the limit once it actually runs.
+            if !matches!(result, ExecutionResult::Stopped(StoppedResult::Blocked)) {
+                self.executed += 1;
+            }
 
             match result {
                 ExecutionResult::Running(None) => {}
@@ -95,98 +201,152 @@ impl Computer {
         }
     }
 
+    /// Reads an absolute address. Memory beyond the current image reads as
+    /// zero.
     pub fn get_memory_value(&self, idx: usize) -> i64 {
-        self.memory[idx]
+        self.memory.get(idx).copied().unwrap_or(0)
     }
 
-    pub fn set_value(&mut self, param: IndexedParameter, value: i64) {
-        match param {
-            IndexedParameter::Positional(idx) => {
-                self.set_memory_value(idx, value);
-            }
-            IndexedParameter::Relative(offset) => {
-                let idx = self.relative_base.wrapping_add(offset);
-                self.set_memory_value(idx, value);
-            }
+    /// The whole memory image, e.g. to search it for data. It starts with the
+    /// program and may be followed by zeroed space that writes can grow into.
+    ///
+    /// ```
+    /// use aoc2019::intcode::Computer;
+    ///
+    /// let mut computer = Computer::new(&[1101, 20, 22, 5, 99]);
+    /// computer.run(vec![])?;
+    /// assert_eq!(computer.memory()[..6], [1101, 20, 22, 5, 99, 42]);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn memory(&self) -> &[i64] {
+        &self.memory
+    }
+
+    /// Writes to memory, e.g. to patch a program before running it.
+    ///
+    /// ```
+    /// use aoc2019::intcode::{Computer, IndexedParameter};
+    ///
+    /// let mut computer = Computer::new(&[1, 0, 0, 0, 99]);
+    /// computer.set_value(IndexedParameter::Positional(1), 4)?;
+    /// computer.run(vec![])?;
+    /// assert_eq!(computer.get_memory_value(0), 100);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn set_value(&mut self, param: IndexedParameter, value: i64) -> Result<()> {
+        let idx = self.resolve(param)?;
+        self.set_memory_value(idx, value)
+    }
+
+    fn resolve(&self, param: IndexedParameter) -> Result<usize> {
+        let idx = match param {
+            IndexedParameter::Positional(idx) => idx as i64,
+            IndexedParameter::Relative(offset) => self.relative_base.wrapping_add(offset),
+        };
+
+        if idx < 0 || idx as usize >= MAX_MEMORY {
+            return Err(anyhow!("Address {} is out of range", idx));
         }
+
+        Ok(idx as usize)
     }
 
     fn do_instruction(
         &mut self,
         instruction: Instruction,
         mut input_stream: impl Iterator<Item = i64>,
-    ) -> ExecutionResult {
-        match instruction {
+    ) -> Result<ExecutionResult> {
+        let result = match instruction {
             Instruction::Add(a, b, Parameter::Indexed(p)) => {
-                self.set_value(p, self.get_param_value(a) + self.get_param_value(b));
+                let value = self
+                    .get_param_value(a)?
+                    .wrapping_add(self.get_param_value(b)?);
+                self.set_value(p, value)?;
                 self.pointer += 4;
                 ExecutionResult::Running(None)
             }
             Instruction::Multiply(a, b, Parameter::Indexed(p)) => {
-                self.set_value(p, self.get_param_value(a) * self.get_param_value(b));
+                let value = self
+                    .get_param_value(a)?
+                    .wrapping_mul(self.get_param_value(b)?);
+                self.set_value(p, value)?;
                 self.pointer += 4;
                 ExecutionResult::Running(None)
             }
             Instruction::Input(Parameter::Indexed(p)) => match input_stream.next() {
                 Some(input) => {
-                    self.set_value(p, input);
+                    self.set_value(p, input)?;
                     self.pointer += 2;
                     ExecutionResult::Running(None)
                 }
                 None => ExecutionResult::Stopped(StoppedResult::Blocked),
             },
             Instruction::Output(a) => {
-                let output = self.get_param_value(a);
+                let output = self.get_param_value(a)?;
                 self.pointer += 2;
                 ExecutionResult::Running(Some(output))
             }
             Instruction::JumpIfTrue(a, b) => {
-                if self.get_param_value(a) != 0 {
-                    self.pointer = self.get_param_value(b) as usize;
+                if self.get_param_value(a)? != 0 {
+                    self.jump(self.get_param_value(b)?)?;
                 } else {
                     self.pointer += 3;
                 }
                 ExecutionResult::Running(None)
             }
             Instruction::JumpIfFalse(a, b) => {
-                if self.get_param_value(a) == 0 {
-                    self.pointer = self.get_param_value(b) as usize;
+                if self.get_param_value(a)? == 0 {
+                    self.jump(self.get_param_value(b)?)?;
                 } else {
                     self.pointer += 3;
                 }
                 ExecutionResult::Running(None)
             }
             Instruction::LessThan(a, b, Parameter::Indexed(p)) => {
-                let value = match self.get_param_value(a) < self.get_param_value(b) {
+                let value = match self.get_param_value(a)? < self.get_param_value(b)? {
                     true => 1,
                     false => 0,
                 };
 
-                self.set_value(p, value);
+                self.set_value(p, value)?;
                 self.pointer += 4;
                 ExecutionResult::Running(None)
             }
             Instruction::Equals(a, b, Parameter::Indexed(p)) => {
-                let value = match self.get_param_value(a) == self.get_param_value(b) {
+                let value = match self.get_param_value(a)? == self.get_param_value(b)? {
                     true => 1,
                     false => 0,
                 };
 
-                self.set_value(p, value);
+                self.set_value(p, value)?;
                 self.pointer += 4;
                 ExecutionResult::Running(None)
             }
             Instruction::AdjustRelativeBase(a) => {
-                self.relative_base = self
-                    .relative_base
-                    .wrapping_add(self.get_param_value(a) as usize);
+                self.relative_base = self.relative_base.wrapping_add(self.get_param_value(a)?);
 
                 self.pointer += 2;
                 ExecutionResult::Running(None)
             }
             Instruction::Halt => ExecutionResult::Stopped(StoppedResult::Halted),
-            _ => unreachable!(),
+            _ => {
+                return Err(anyhow!(
+                    "Immediate mode write parameter at {}",
+                    self.pointer
+                ))
+            }
+        };
+
+        Ok(result)
+    }
+
+    fn jump(&mut self, target: i64) -> Result<()> {
+        if target < 0 || target as usize >= MAX_MEMORY {
+            return Err(anyhow!("Jump to out of range address {}", target));
         }
+
+        self.pointer = target as usize;
+        Ok(())
     }
 
     fn get_instruction(&self) -> Result<Instruction> {
@@ -234,28 +394,37 @@ impl Computer {
 
         let data = self.get_memory_value(self.pointer + offset + 1);
         match flag {
+            0 if data < 0 => Err(anyhow!("Negative address {}", data)),
             0 => Ok(Parameter::Indexed(IndexedParameter::Positional(
                 data as usize,
             ))),
             1 => Ok(Parameter::Immediate(data)),
-            2 => Ok(Parameter::Indexed(IndexedParameter::Relative(
-                data as usize,
-            ))),
+            2 => Ok(Parameter::Indexed(IndexedParameter::Relative(data))),
             _ => Err(anyhow!("Unrecognized parameter flag {}", flag)),
         }
     }
 
-    fn get_param_value(&self, param: Parameter) -> i64 {
+    fn get_param_value(&self, param: Parameter) -> Result<i64> {
         match param {
-            Parameter::Indexed(IndexedParameter::Positional(idx)) => self.get_memory_value(idx),
-            Parameter::Immediate(data) => data,
-            Parameter::Indexed(IndexedParameter::Relative(offset)) => {
-                self.get_memory_value(self.relative_base.wrapping_add(offset))
-            }
+            Parameter::Immediate(data) => Ok(data),
+            Parameter::Indexed(indexed) => Ok(self.get_memory_value(self.resolve(indexed)?)),
         }
     }
 
-    fn set_memory_value(&mut self, idx: usize, value: i64) {
+    fn set_memory_value(&mut self, idx: usize, value: i64) -> Result<()> {
+        if idx >= self.memory.len() {
+            if idx >= MAX_MEMORY {
+                return Err(anyhow!("Address {} is out of range", idx));
+            }
+
+            let size = (idx + 1).next_power_of_two().min(MAX_MEMORY);
+            self.memory.resize(size, 0);
+        }
+
         self.memory[idx] = value;
+        Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,359 @@
+use super::*;
+use proptest::prelude::*;
+use std::collections::HashMap;
+
+const LIMIT: u64 = 10_000;
+
+// Every value the generated programs can touch falls in this window, so
+// comparing it is enough to compare final memory.
+const WINDOW: usize = 256;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Outcome {
+    Halted(Vec<i64>),
+    Blocked(Vec<i64>),
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Trace {
+    outcome: Outcome,
+    memory: Vec<i64>,
+}
+
+impl Trace {
+    fn new(result: Result<(StoppedResult, Vec<i64>)>, memory: Vec<i64>) -> Self {
+        let outcome = match result {
+            Ok((StoppedResult::Halted, output)) => Outcome::Halted(output),
+            Ok((StoppedResult::Blocked, output)) => Outcome::Blocked(output),
+            Err(_) => Outcome::Failed,
+        };
+
+        Self { outcome, memory }
+    }
+}
+
+fn snapshot(computer: &Computer) -> Vec<i64> {
+    (0..WINDOW)
+        .map(|idx| computer.get_memory_value(idx))
+        .collect()
+}
+
+fn limited(program: &[i64]) -> Computer {
+    let mut computer = Computer::new(program);
+    computer.set_instruction_limit(Some(LIMIT));
+    computer
+}
+
+// Runs with every input available up front.
+fn run_batch(program: &[i64], input: &[i64]) -> Trace {
+    let mut computer = limited(program);
+    let result = computer.run_until_stopped(input.to_vec());
+    Trace::new(result, snapshot(&computer))
+}
+
+// Runs with no input and resumes with a single value each time it blocks.
+fn run_stepwise(program: &[i64], input: &[i64]) -> Trace {
+    let mut computer = limited(program);
+    let mut output = vec![];
+    let mut pending = input.iter();
+    let mut next = vec![];
+
+    let result = loop {
+        match computer.run_until_stopped(next) {
+            Ok((StoppedResult::Blocked, chunk)) => {
+                output.extend(chunk);
+                match pending.next() {
+                    Some(&value) => next = vec![value],
+                    None => break Ok((StoppedResult::Blocked, output)),
+                }
+            }
+            Ok((StoppedResult::Halted, chunk)) => {
+                output.extend(chunk);
+                break Ok((StoppedResult::Halted, output));
+            }
+            Err(error) => break Err(error),
+        }
+    };
+
+    Trace::new(result, snapshot(&computer))
+}
+
+// A deliberately simple interpreter over sparse memory, written from the
+// puzzle description rather than sharing any code with `Computer`.
+fn run_reference(program: &[i64], input: &[i64]) -> Trace {
+    let mut memory: HashMap<i64, i64> = program
+        .iter()
+        .enumerate()
+        .map(|(idx, &value)| (idx as i64, value))
+        .collect();
+    let mut input = input.iter();
+    let mut output = vec![];
+    let mut pointer = 0i64;
+    let mut base = 0i64;
+    let max = MAX_MEMORY as i64;
+
+    let outcome = 'run: loop {
+        for step in 0.. {
+            let read = |memory: &HashMap<i64, i64>, idx: i64| *memory.get(&idx).unwrap_or(&0);
+            let instruction = read(&memory, pointer);
+            let opcode = instruction % 100;
+            let arity = match opcode {
+                1 | 2 | 7 | 8 => 3,
+                5 | 6 => 2,
+                3 | 4 | 9 => 1,
+                99 => 0,
+                _ => break 'run Outcome::Failed,
+            };
+
+            // Each parameter decodes to its mode and raw value.
+            let mut params = vec![];
+            for k in 0..arity {
+                let mode = instruction / 10i64.pow(k as u32 + 2) % 10;
+                let raw = read(&memory, pointer + k + 1);
+                if !(0..=2).contains(&mode) || (mode == 0 && raw < 0) {
+                    break 'run Outcome::Failed;
+                }
+                params.push((mode, raw));
+            }
+
+            let address = |(mode, raw): (i64, i64)| {
+                let idx = if mode == 2 {
+                    base.wrapping_add(raw)
+                } else {
+                    raw
+                };
+                Some(idx).filter(|idx| (0..max).contains(idx))
+            };
+            let value = |memory: &HashMap<i64, i64>, param: (i64, i64)| match param.0 {
+                1 => Some(param.1),
+                _ => address(param).map(|idx| read(memory, idx)),
+            };
+            let target = |param: (i64, i64)| match param.0 {
+                1 => None,
+                _ => address(param),
+            };
+
+            if step as u64 >= LIMIT {
+                break 'run Outcome::Failed;
+            }
+
+            let next = pointer + 1 + arity;
+            match opcode {
+                1 | 2 | 7 | 8 => {
+                    let (a, b, c) = match (
+                        value(&memory, params[0]),
+                        value(&memory, params[1]),
+                        target(params[2]),
+                    ) {
+                        (Some(a), Some(b), Some(c)) => (a, b, c),
+                        _ => break 'run Outcome::Failed,
+                    };
+                    let result = match opcode {
+                        1 => a.wrapping_add(b),
+                        2 => a.wrapping_mul(b),
+                        7 => (a < b) as i64,
+                        _ => (a == b) as i64,
+                    };
+                    memory.insert(c, result);
+                    pointer = next;
+                }
+                3 => {
+                    if params[0].0 == 1 {
+                        break 'run Outcome::Failed;
+                    }
+                    let value = match input.next() {
+                        Some(&value) => value,
+                        None => break 'run Outcome::Blocked(output),
+                    };
+                    match target(params[0]) {
+                        Some(idx) => memory.insert(idx, value),
+                        None => break 'run Outcome::Failed,
+                    };
+                    pointer = next;
+                }
+                4 => {
+                    match value(&memory, params[0]) {
+                        Some(value) => output.push(value),
+                        None => break 'run Outcome::Failed,
+                    }
+                    pointer = next;
+                }
+                5 | 6 => {
+                    let condition = match value(&memory, params[0]) {
+                        Some(condition) => condition,
+                        None => break 'run Outcome::Failed,
+                    };
+                    if (condition != 0) == (opcode == 5) {
+                        match value(&memory, params[1]) {
+                            Some(jump) if (0..max).contains(&jump) => pointer = jump,
+                            _ => break 'run Outcome::Failed,
+                        }
+                    } else {
+                        pointer = next;
+                    }
+                }
+                9 => {
+                    match value(&memory, params[0]) {
+                        Some(offset) => base = base.wrapping_add(offset),
+                        None => break 'run Outcome::Failed,
+                    }
+                    pointer = next;
+                }
+                _ => break 'run Outcome::Halted(output),
+            }
+        }
+    };
+
+    let memory = (0..WINDOW as i64)
+        .map(|idx| *memory.get(&idx).unwrap_or(&0))
+        .collect();
+    Trace { outcome, memory }
+}
+
+// Encodes an opcode with a mode digit per parameter.
+fn encode(opcode: i64, modes: &[i64]) -> i64 {
+    modes.iter().enumerate().fold(opcode, |acc, (k, mode)| {
+        acc + mode * 10i64.pow(k as u32 + 2)
+    })
+}
+
+fn read_param() -> impl Strategy<Value = (i64, i64)> {
+    prop_oneof![
+        (0i64..WINDOW as i64).prop_map(|address| (0, address)),
+        (-100i64..100).prop_map(|value| (1, value)),
+        (-8i64..64).prop_map(|offset| (2, offset)),
+    ]
+}
+
+// Write parameters stay in position or relative mode, and any address in the
+// window may overwrite the program itself.
+fn write_param() -> impl Strategy<Value = (i64, i64)> {
+    prop_oneof![
+        (0i64..WINDOW as i64).prop_map(|address| (0, address)),
+        (-8i64..64).prop_map(|offset| (2, offset)),
+    ]
+}
+
+fn jump_param() -> impl Strategy<Value = (i64, i64)> {
+    prop_oneof![(0i64..96).prop_map(|target| (1, target)), read_param(),]
+}
+
+fn instruction() -> impl Strategy<Value = Vec<i64>> {
+    let with_params = |opcode: i64, params: Vec<(i64, i64)>| {
+        let modes: Vec<i64> = params.iter().map(|&(mode, _)| mode).collect();
+        std::iter::once(encode(opcode, &modes))
+            .chain(params.into_iter().map(|(_, value)| value))
+            .collect::<Vec<_>>()
+    };
+
+    prop_oneof![
+        (
+            prop_oneof![Just(1i64), Just(2), Just(7), Just(8)],
+            read_param(),
+            read_param(),
+            write_param()
+        )
+            .prop_map(move |(opcode, a, b, c)| with_params(opcode, vec![a, b, c])),
+        write_param().prop_map(move |a| with_params(3, vec![a])),
+        read_param().prop_map(move |a| with_params(4, vec![a])),
+        (prop_oneof![Just(5i64), Just(6)], read_param(), jump_param())
+            .prop_map(move |(opcode, a, b)| with_params(opcode, vec![a, b])),
+        read_param().prop_map(move |a| with_params(9, vec![a])),
+    ]
+}
+
+fn program() -> impl Strategy<Value = Vec<i64>> {
+    prop::collection::vec(instruction(), 1..24).prop_map(|instructions| {
+        let mut program: Vec<i64> = instructions.into_iter().flatten().collect();
+        program.push(99);
+        program
+    })
+}
+
+fn inputs() -> impl Strategy<Value = Vec<i64>> {
+    prop::collection::vec(-50i64..50, 0..8)
+}
+
+proptest! {
+    #[test]
+    fn prop_deterministic(program in program(), input in inputs()) {
+        prop_assert_eq!(run_batch(&program, &input), run_batch(&program, &input));
+    }
+
+    #[test]
+    fn prop_stepwise_matches_batch(program in program(), input in inputs()) {
+        prop_assert_eq!(run_stepwise(&program, &input), run_batch(&program, &input));
+    }
+
+    #[test]
+    fn prop_matches_reference(program in program(), input in inputs()) {
+        prop_assert_eq!(run_batch(&program, &input), run_reference(&program, &input));
+    }
+
+    #[test]
+    fn prop_clone_is_equivalent(program in program(), input in inputs()) {
+        let mut computer = limited(&program);
+        if computer.run_until_stopped(vec![]).is_ok() {
+            let mut clone = computer.clone();
+            let original = computer.run_until_stopped(input.clone());
+            let cloned = clone.run_until_stopped(input);
+            prop_assert_eq!(
+                Trace::new(original, snapshot(&computer)),
+                Trace::new(cloned, snapshot(&clone))
+            );
+        }
+    }
+
+    #[test]
+    fn prop_arbitrary_memory_never_panics(
+        memory in prop::collection::vec(any::<i64>(), 0..64),
+        input in prop::collection::vec(any::<i64>(), 0..8),
+    ) {
+        let _ = limited(&memory).run_until_stopped(input);
+    }
+
+    #[test]
+    fn prop_arbitrary_source_never_panics(source in "[-0-9, \n]{0,64}|\\PC{0,64}") {
+        if let Ok(mut computer) = Computer::new_from_str(&source) {
+            computer.set_instruction_limit(Some(LIMIT));
+            let _ = computer.run(vec![1, 2, 3]);
+        }
+    }
+}
+
+#[test]
+fn test_invalid_programs_fail() -> Result<()> {
+    for source in &["", "1,2", "3,0,4,x,99", "1101,1,1,-1,99", "1,0,0,0,42"] {
+        let result = Computer::new_from_str(source).and_then(|mut c| c.run(vec![1]));
+        assert!(result.is_err(), "{:?} should fail", source);
+    }
+
+    // Writes to immediate parameters, far addresses and negative jumps.
+    for program in &[
+        vec![11101, 1, 1, 0, 99],
+        vec![1101, 1, 1, MAX_MEMORY as i64, 99],
+        vec![1105, 1, -1],
+        vec![109, -5, 204, 0, 99],
+    ] {
+        assert!(Computer::new(program).run(vec![]).is_err());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_memory_grows_on_write() -> Result<()> {
+    let far = 100_000;
+    let program = vec![1101, 7, 8, far, 4, far, 99];
+    assert_eq!(Computer::new(&program).run(vec![])?, vec![15]);
+    Ok(())
+}
+
+#[test]
+fn test_instruction_limit() -> Result<()> {
+    let mut computer = Computer::new_from_str("1105,1,0")?;
+    computer.set_instruction_limit(Some(1000));
+    assert!(computer.run(vec![]).is_err());
+    Ok(())
+}
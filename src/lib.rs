@@ -0,0 +1,23 @@
+//! Solutions and shared tooling for Advent of Code 2019.
+//!
+//! The [`intcode`] virtual machine and the [`common`] geometry and game helpers
+//! are the library's stable API, and breaking changes to them follow semantic
+//! versioning. The [`days`], [`input`] and [`commands`] modules are public so
+//! that the `aoc2019` command line tool and the benchmarks can use them; they
+//! follow the tool and are not part of that API.
+//!
+//! ```
+//! use aoc2019::intcode::Computer;
+//!
+//! // Adds its two inputs and outputs the sum.
+//! let mut computer = Computer::new_from_str("3,11,3,12,1,11,12,13,4,13,99")?;
+//! assert_eq!(computer.run(vec![2, 3])?, vec![5]);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+pub mod commands;
+pub mod common;
+pub mod days;
+pub mod input;
+pub mod intcode;
+mod verify;
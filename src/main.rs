@@ -1,48 +1,278 @@
-use anyhow::{anyhow, Result};
-use std::io::Read;
+use anyhow::Result;
+use aoc2019::commands;
+use aoc2019::days::{self, Solution};
+use aoc2019::input::{self, InputStore};
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use structopt::clap::{self, AppSettings};
 use structopt::StructOpt;
 
-mod common;
-mod days;
-mod intcode;
-
 #[derive(Debug, StructOpt)]
+#[structopt(setting = AppSettings::ArgsNegateSubcommands)]
 struct Opt {
-    #[structopt()]
-    question: u32,
+    /// Day to run; required unless a subcommand is given
+    question: Option<u32>,
+
+    /// Part to run; required unless a subcommand is given
+    part: Option<u32>,
+
+    /// Number of simulation steps, for days that support overriding it
+    #[structopt(long)]
+    steps: Option<usize>,
 
-    #[structopt()]
-    part: u32,
+    /// Read the puzzle input from this file, or `-` for stdin
+    #[structopt(long, parse(from_os_str))]
+    input: Option<PathBuf>,
+
+    #[structopt(flatten)]
+    store: StoreOpt,
+
+    #[structopt(subcommand)]
+    command: Option<Command>,
 }
 
-struct Parts(fn(&str) -> Result<String>, fn(&str) -> Result<String>);
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// List the implemented days
+    List,
+
+    /// Run every registered example and compare against its published answer
+    Examples,
 
-fn main() -> Result<()> {
-    let args = Opt::from_args();
+    /// Run both parts of every implemented day and print a timing table
+    All {
+        #[structopt(flatten)]
+        store: StoreOpt,
+    },
 
-    let mut input = String::new();
-    std::io::stdin().read_to_string(&mut input)?;
-
-    let question = args.question;
-    let part = args.part;
-
-    let parts = match question {
-        1 => Ok(Parts(days::day01::part1, days::day01::part2)),
-        2 => Ok(Parts(days::day02::part1, days::day02::part2)),
-        5 => Ok(Parts(days::day05::part1, days::day05::part2)),
-        9 => Ok(Parts(days::day09::part1, days::day09::part2)),
-        11 => Ok(Parts(days::day11::part1, days::day11::part2)),
-        13 => Ok(Parts(days::day13::part1, days::day13::part2)),
-        15 => Ok(Parts(days::day15::part1, days::day15::part2)),
-        _ => Err(anyhow!("Question {} not implemented", question)),
-    }?;
+    /// Check every day with an input against the recorded answers
+    Verify {
+        /// Recorded answers, keyed by day and part
+        #[structopt(long, default_value = "answers.toml", parse(from_os_str))]
+        answers: PathBuf,
+
+        /// Record answers for parts that have none yet
+        #[structopt(long)]
+        record: bool,
+
+        #[structopt(flatten)]
+        store: StoreOpt,
+    },
+
+    /// Play the day 13 arcade game in the terminal
+    #[cfg(feature = "arcade")]
+    Arcade {
+        /// Start with the autopilot steering the paddle
+        #[structopt(long)]
+        autoplay: bool,
+
+        /// Milliseconds per frame
+        #[structopt(long, default_value = "100")]
+        frame_ms: u64,
+
+        /// Save a replay of the game to this file
+        #[structopt(long, parse(from_os_str))]
+        record: Option<PathBuf>,
+
+        /// Read the program from this file instead of the input store
+        #[structopt(long, parse(from_os_str))]
+        input: Option<PathBuf>,
+
+        #[structopt(flatten)]
+        store: StoreOpt,
+    },
+
+    /// Play the day 13 arcade game with each autopilot strategy and compare them
+    Autopilot {
+        /// Read the program from this file, or `-` for stdin
+        #[structopt(long, parse(from_os_str))]
+        input: Option<PathBuf>,
+
+        #[structopt(flatten)]
+        store: StoreOpt,
+    },
+
+    /// Find the arcade game's tile map in memory, and optionally play it with
+    /// the paddle's row walled off
+    Hack {
+        /// Play the patched game with the joystick left alone
+        #[structopt(long)]
+        play: bool,
+
+        /// Read the program from this file, or `-` for stdin
+        #[structopt(long, parse(from_os_str))]
+        input: Option<PathBuf>,
+
+        #[structopt(flatten)]
+        store: StoreOpt,
+    },
+
+    /// Replay a recorded arcade game and check that it reaches the same scores
+    Replay {
+        /// Replay file saved by `arcade --record`
+        #[structopt(parse(from_os_str))]
+        replay: PathBuf,
+
+        /// Write every frame as text to this file
+        #[structopt(long, parse(from_os_str))]
+        text: Option<PathBuf>,
 
-    let result = match part {
-        1 => parts.0(&input),
-        2 => parts.1(&input),
-        _ => Err(anyhow!("Part {} not implemented", part)),
+        /// Write every frame as a PPM image into this directory
+        #[structopt(long, parse(from_os_str))]
+        ppm: Option<PathBuf>,
+
+        /// Pixels per tile in PPM images
+        #[structopt(long, default_value = "8")]
+        scale: usize,
+
+        /// Read the program from this file instead of the input store
+        #[structopt(long, parse(from_os_str))]
+        input: Option<PathBuf>,
+
+        #[structopt(flatten)]
+        store: StoreOpt,
+    },
+
+    /// Copy a day's input into the offline input cache
+    Cache {
+        day: u32,
+
+        /// File to cache; reads stdin when omitted
+        #[structopt(long, parse(from_os_str))]
+        input: Option<PathBuf>,
+
+        #[structopt(flatten)]
+        store: StoreOpt,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+struct StoreOpt {
+    /// Directory containing inputs named `qNN.txt`
+    #[structopt(long, default_value = "inputs", parse(from_os_str))]
+    inputs: PathBuf,
+
+    /// Offline input cache, also named `qNN.txt` [default: ~/.cache/aoc2019/inputs]
+    #[structopt(long, env = "AOC2019_INPUT_CACHE", parse(from_os_str))]
+    cache_dir: Option<PathBuf>,
+}
+
+impl StoreOpt {
+    fn into_store(self) -> InputStore {
+        InputStore::new(self.inputs, self.cache_dir)
+    }
+}
+
+// An explicit `--input` wins, then piped stdin, then the input store. Empty
+// stdin, as in scripts and CI, falls through to the store.
+fn load_input(day: u32, path: Option<PathBuf>, store: &InputStore) -> Result<String> {
+    match path {
+        Some(ref path) if path.as_os_str() == "-" => input::read_stdin(),
+        Some(path) => input::read_file(&path),
+        None => {
+            if !std::io::stdin().is_terminal() {
+                if let Some(input) = input::read_stdin_if_provided()? {
+                    return Ok(input);
+                }
+            }
+
+            store.load(day)
+        }
+    }
+}
+
+fn run_one(solution: &dyn Solution, part: u32, steps: Option<usize>, input: String) -> Result<()> {
+    let result = match steps {
+        Some(steps) => solution.run_with_steps(part, &input, steps),
+        None => solution.run(part, &input),
     }?;
 
     println!("{}", result);
     Ok(())
 }
+
+fn main() -> Result<()> {
+    let args = Opt::from_args();
+
+    match (args.command, args.question, args.part) {
+        (Some(Command::List), _, _) => {
+            commands::list();
+            Ok(())
+        }
+        (Some(Command::Examples), _, _) => commands::run_examples(),
+        (Some(Command::All { store }), _, _) => commands::run_all(&store.into_store()),
+        (
+            Some(Command::Verify {
+                answers,
+                record,
+                store,
+            }),
+            _,
+            _,
+        ) => commands::run_verify(&answers, record, &store.into_store()),
+        #[cfg(feature = "arcade")]
+        (
+            Some(Command::Arcade {
+                autoplay,
+                frame_ms,
+                record,
+                input,
+                store,
+            }),
+            _,
+            _,
+        ) => {
+            // Stdin is the keyboard here, so the program never comes from it.
+            let source = match input {
+                Some(path) => input::read_file(&path)?,
+                None => store.into_store().load(13)?,
+            };
+            commands::run_arcade(&source, autoplay, frame_ms, record)
+        }
+        (Some(Command::Autopilot { input, store }), _, _) => {
+            let source = load_input(13, input, &store.into_store())?;
+            commands::compare_autopilots(&source)
+        }
+        (Some(Command::Hack { play, input, store }), _, _) => {
+            let source = load_input(13, input, &store.into_store())?;
+            commands::run_hack(&source, play)
+        }
+        (
+            Some(Command::Replay {
+                replay,
+                text,
+                ppm,
+                scale,
+                input,
+                store,
+            }),
+            _,
+            _,
+        ) => {
+            let source = load_input(13, input, &store.into_store())?;
+            commands::run_replay(&source, &replay, text, ppm, scale)
+        }
+        (Some(Command::Cache { day, input, store }), _, _) => {
+            let contents = match input {
+                Some(path) => input::read_file(&path)?,
+                None => input::read_stdin()?,
+            };
+
+            let path = store.into_store().store(day, &contents)?;
+            println!("Cached day {} input at {}", day, path.display());
+            Ok(())
+        }
+        (None, Some(question), Some(part)) => {
+            // Look the day up first, so an unimplemented day is reported as
+            // such rather than as a missing input.
+            let solution = days::get_solution(question)?;
+            let input = load_input(question, args.input, &args.store.into_store())?;
+            run_one(solution, part, args.steps, input)
+        }
+        _ => clap::Error::with_description(
+            "Expected a <question> and <part>, or a subcommand",
+            clap::ErrorKind::MissingRequiredArgument,
+        )
+        .exit(),
+    }
+}
@@ -0,0 +1,223 @@
+use crate::days::Solution;
+use crate::input::{self, InputStore};
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+// Recorded answers, stored as TOML tables keyed by day with one string per
+// part, e.g. `[day01]` followed by `part1 = "3147032"`.
+#[derive(Debug, Clone, Default)]
+pub struct Answers {
+    days: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+fn day_key(day: u32) -> String {
+    format!("day{:02}", day)
+}
+
+fn part_key(part: u32) -> String {
+    format!("part{}", part)
+}
+
+impl Answers {
+    // A missing file is treated as having no recorded answers.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Unable to read answers file {}", path.display()))?;
+        let days = toml::from_str(&contents)
+            .with_context(|| format!("Invalid answers file {}", path.display()))?;
+
+        Ok(Self { days })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let contents = toml::to_string(&self.days)?;
+        fs::write(path, contents)
+            .with_context(|| format!("Unable to write answers file {}", path.display()))
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.days
+            .get(&day_key(day))
+            .and_then(|parts| parts.get(&part_key(part)))
+            .map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u32, part: u32, answer: String) {
+        self.days
+            .entry(day_key(day))
+            .or_default()
+            .insert(part_key(part), answer);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Match,
+    Mismatch { expected: String, actual: String },
+    New(String),
+    Failed(String),
+    MissingInput,
+}
+
+#[derive(Debug, Clone)]
+pub struct Verification {
+    pub day: u32,
+    pub part: u32,
+    pub outcome: Outcome,
+}
+
+pub fn check(solution: &dyn Solution, part: u32, input: &str, answers: &Answers) -> Outcome {
+    let actual = match solution.run(part, input) {
+        Ok(actual) => actual,
+        Err(error) => return Outcome::Failed(format!("{:#}", error)),
+    };
+
+    match answers.get(solution.day(), part) {
+        Some(expected) if expected == actual => Outcome::Match,
+        Some(expected) => Outcome::Mismatch {
+            expected: expected.to_string(),
+            actual,
+        },
+        None => Outcome::New(actual),
+    }
+}
+
+// An input that exists but cannot be read fails both parts of its day
+// without stopping the other days.
+pub fn verify_all(
+    solutions: &[&dyn Solution],
+    store: &InputStore,
+    answers: &Answers,
+) -> Vec<Verification> {
+    let mut results = vec![];
+
+    for solution in solutions {
+        let day = solution.day();
+        let input = store.find(day).map(|path| input::read_file(&path));
+
+        for part in 1..=2 {
+            let outcome = match input {
+                Some(Ok(ref input)) => check(*solution, part, input, answers),
+                Some(Err(ref error)) => Outcome::Failed(format!("{:#}", error)),
+                None => Outcome::MissingInput,
+            };
+
+            results.push(Verification { day, part, outcome });
+        }
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::{day01::Day01, SOLUTIONS};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_check_outcomes() {
+        let mut answers = Answers::default();
+        answers.insert(1, 1, "2".to_string());
+        answers.insert(1, 2, "3".to_string());
+
+        assert_eq!(check(&Day01, 1, "12\n", &answers), Outcome::Match);
+        assert_eq!(
+            check(&Day01, 2, "12\n", &answers),
+            Outcome::Mismatch {
+                expected: "3".to_string(),
+                actual: "2".to_string()
+            }
+        );
+        assert_eq!(
+            check(&Day01, 1, "14\n", &Answers::default()),
+            Outcome::New("2".to_string())
+        );
+        assert_eq!(
+            check(&Day01, 3, "14\n", &answers),
+            Outcome::Failed("Part 3 not implemented".to_string())
+        );
+    }
+
+    #[test]
+    fn test_answers_round_trip() -> Result<()> {
+        let path =
+            std::env::temp_dir().join(format!("aoc2019-answers-{}.toml", std::process::id()));
+
+        let mut answers = Answers::default();
+        answers.insert(11, 2, " ## \n#  #".to_string());
+        answers.insert(1, 1, "34241".to_string());
+        answers.save(&path)?;
+
+        let loaded = Answers::load(&path)?;
+        assert_eq!(loaded.get(1, 1), Some("34241"));
+        assert_eq!(loaded.get(11, 2), Some(" ## \n#  #"));
+        assert_eq!(loaded.get(1, 2), None);
+
+        fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_unreadable_input_fails_its_day() -> Result<()> {
+        let inputs =
+            std::env::temp_dir().join(format!("aoc2019-unreadable-{}", std::process::id()));
+        fs::create_dir_all(&inputs)?;
+        fs::write(inputs.join("q01.txt"), [0xff, 0xfe])?;
+        fs::write(inputs.join("q02.txt"), "1,0,0,0,99,0,0,0,0,0,0,0,0")?;
+
+        let store = InputStore::local(inputs.clone());
+        let results = verify_all(SOLUTIONS, &store, &Answers::default());
+        let outcome = |day, part| {
+            results
+                .iter()
+                .find(|result| result.day == day && result.part == part)
+                .map(|result| result.outcome.clone())
+        };
+
+        assert!(matches!(outcome(1, 1), Some(Outcome::Failed(_))));
+        assert!(matches!(outcome(1, 2), Some(Outcome::Failed(_))));
+        assert!(matches!(outcome(2, 1), Some(Outcome::New(_))));
+        assert_eq!(outcome(5, 1), Some(Outcome::MissingInput));
+
+        fs::remove_dir_all(inputs)?;
+        Ok(())
+    }
+
+    // Checks every solution with a local input against the recorded answers in
+    // the repository root. Neither is committed, so this only runs with
+    // `cargo test -- --ignored`, and fails if it had nothing to check.
+    #[test]
+    #[ignore = "needs inputs/ and answers.toml"]
+    fn test_recorded_answers() -> Result<()> {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let answers = Answers::load(&root.join("answers.toml"))?;
+        let store = InputStore::local(root.join("inputs"));
+        let mut matched = 0;
+
+        for result in verify_all(SOLUTIONS, &store, &answers) {
+            match result.outcome {
+                Outcome::Match => matched += 1,
+                Outcome::Mismatch { .. } | Outcome::Failed(_) => {
+                    panic!(
+                        "day {} part {}: {:?}",
+                        result.day, result.part, result.outcome
+                    )
+                }
+                _ => {}
+            }
+        }
+
+        assert!(
+            matched > 0,
+            "No recorded answers had a local input to check"
+        );
+        Ok(())
+    }
+}